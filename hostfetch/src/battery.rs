@@ -0,0 +1,363 @@
+use std::fs;
+use std::path::Path;
+
+use crate::config::{Config, UptimeStyle};
+use crate::module::{list_items, paint_info, InfoModule, ModuleData, ModuleError, Value};
+use crate::uptime::format_uptime;
+
+const SYSFS_POWER_SUPPLY: &str = "/sys/class/power_supply";
+
+#[derive(Debug, Clone)]
+pub struct Battery {
+    pub name: String,
+    pub status: String,
+    pub capacity: f64,
+    pub energy_now: Option<u64>,
+    pub energy_full: Option<u64>,
+    pub energy_full_design: Option<u64>,
+    pub power_now: Option<u64>,
+    pub cycle_count: Option<u64>,
+}
+
+impl Battery {
+    pub fn is_charging(&self) -> bool {
+        self.status == "Charging"
+    }
+
+    pub fn is_discharging(&self) -> bool {
+        self.status == "Discharging"
+    }
+
+    pub fn time_remaining(&self) -> Option<u64> {
+        let now = self.energy_now? as f64;
+        let rate = self.power_now.filter(|&rate| rate > 0)? as f64;
+        let hours = if self.is_discharging() {
+            now / rate
+        } else if self.is_charging() {
+            (self.energy_full? as f64 - now).max(0.0) / rate
+        } else {
+            return None;
+        };
+        Some((hours * 3600.0) as u64)
+    }
+
+    pub fn health(&self) -> Option<f64> {
+        let full = self.energy_full? as f64;
+        let design = self.energy_full_design.filter(|&design| design > 0)? as f64;
+        Some(full / design * 100.0)
+    }
+}
+
+pub fn get_batteries() -> Vec<Battery> {
+    read_batteries(Path::new(SYSFS_POWER_SUPPLY))
+}
+
+pub fn read_batteries(power_supply: &Path) -> Vec<Battery> {
+    let Ok(entries) = fs::read_dir(power_supply) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .filter_map(|name| read_battery(&power_supply.join(&name), name))
+        .collect()
+}
+
+fn read_battery(dir: &Path, name: String) -> Option<Battery> {
+    let read = |file: &str| {
+        fs::read_to_string(dir.join(file))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let number = |file: &str| read(file).and_then(|value| value.parse::<u64>().ok());
+
+    // Wireless mice and keyboards also report as batteries, but with a
+    // "Device" scope.
+    if read("type")? != "Battery" || read("scope").as_deref() == Some("Device") {
+        return None;
+    }
+    if read("present").as_deref() == Some("0") {
+        return None;
+    }
+
+    // Drivers report either energy (µWh, µW) or charge (µAh, µA). Charge is
+    // converted with the design voltage so batteries can be summed.
+    let voltage = number("voltage_min_design").or_else(|| number("voltage_now"));
+    let energy = |energy_file: &str, charge_file: &str| {
+        number(energy_file).or_else(|| {
+            let charge = number(charge_file)?;
+            Some(match voltage {
+                Some(voltage) => charge * voltage / 1_000_000,
+                None => charge,
+            })
+        })
+    };
+
+    let energy_now = energy("energy_now", "charge_now");
+    let energy_full = energy("energy_full", "charge_full");
+    let capacity = match (number("capacity"), energy_now, energy_full) {
+        (Some(capacity), _, _) => capacity as f64,
+        (None, Some(now), Some(full)) if full > 0 => now as f64 / full as f64 * 100.0,
+        _ => return None,
+    };
+
+    Some(Battery {
+        name,
+        status: read("status").unwrap_or_else(|| "Unknown".to_string()),
+        capacity,
+        energy_now,
+        energy_full,
+        energy_full_design: energy("energy_full_design", "charge_full_design"),
+        power_now: energy("power_now", "current_now"),
+        cycle_count: number("cycle_count").filter(|&count| count > 0),
+    })
+}
+
+pub fn combine(batteries: &[Battery]) -> Option<Battery> {
+    let first = batteries.first()?;
+    if batteries.len() == 1 {
+        return Some(first.clone());
+    }
+
+    let sum = |field: fn(&Battery) -> Option<u64>| -> Option<u64> {
+        batteries.iter().map(field).sum()
+    };
+    let energy_now = sum(|b| b.energy_now);
+    let energy_full = sum(|b| b.energy_full);
+
+    let capacity = match (energy_now, energy_full) {
+        (Some(now), Some(full)) if full > 0 => now as f64 / full as f64 * 100.0,
+        _ => batteries.iter().map(|b| b.capacity).sum::<f64>() / batteries.len() as f64,
+    };
+
+    let status = if batteries.iter().any(Battery::is_charging) {
+        "Charging"
+    } else if batteries.iter().any(Battery::is_discharging) {
+        "Discharging"
+    } else {
+        &first.status
+    };
+
+    Some(Battery {
+        name: "total".to_string(),
+        status: status.to_string(),
+        capacity,
+        energy_now,
+        energy_full,
+        energy_full_design: sum(|b| b.energy_full_design),
+        power_now: sum(|b| b.power_now),
+        cycle_count: batteries.iter().filter_map(|b| b.cycle_count).max(),
+    })
+}
+
+fn format_time_remaining(battery: &ModuleData) -> Option<String> {
+    let seconds = battery.get("time_remaining").and_then(Value::as_u64)?;
+    (seconds >= 60).then(|| format_uptime(seconds, UptimeStyle::Short))
+}
+
+fn describe(battery: &ModuleData, named: bool) -> String {
+    let mut details = vec![battery.text("status")];
+    if let Some(left) = format_time_remaining(battery) {
+        if battery.text("status") == "Charging" {
+            details.push(format!("{} until full", left));
+        } else {
+            details.push(format!("{} left", left));
+        }
+    }
+    if let Some(health) = battery.get("health") {
+        details.push(format!("health {}", health));
+    }
+    if let Some(cycles) = battery.get("cycle_count") {
+        details.push(format!("{} cycles", cycles));
+    }
+
+    let mut text = format!("{} ({})", battery.text("percent"), details.join(", "));
+    if named {
+        text = format!("{}: {}", battery.text("name"), text);
+    }
+    text
+}
+
+pub struct BatteryModule {
+    combine: bool,
+}
+
+impl BatteryModule {
+    pub fn new(cfg: &Config) -> Self {
+        Self {
+            combine: cfg.battery.combine,
+        }
+    }
+}
+
+impl InfoModule for BatteryModule {
+    fn id(&self) -> &'static str {
+        "battery"
+    }
+
+    fn label(&self) -> &'static str {
+        "Battery"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f0079}"
+    }
+
+    // A machine without batteries yields an empty list rather than an error,
+    // so desktops never show a placeholder for it.
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let mut batteries = get_batteries();
+        if self.combine {
+            batteries = combine(&batteries).into_iter().collect();
+        }
+
+        let batteries = batteries
+            .into_iter()
+            .map(|battery| {
+                let mut data = ModuleData::default()
+                    .with("name", battery.name.as_str())
+                    .with("percent", Value::Percent(battery.capacity))
+                    .with("status", battery.status.as_str());
+                if let Some(seconds) = battery.time_remaining() {
+                    data = data.with("time_remaining", Value::Integer(seconds));
+                }
+                if let Some(health) = battery.health() {
+                    data = data.with("health", Value::Percent(health));
+                }
+                if let Some(cycles) = battery.cycle_count {
+                    data = data.with("cycle_count", Value::Integer(cycles));
+                }
+                data
+            })
+            .collect();
+
+        Ok(ModuleData::default().with("batteries", Value::List(batteries)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        self.render_lines(data, cfg).join(", ")
+    }
+
+    fn render_lines(&self, data: &ModuleData, cfg: &Config) -> Vec<String> {
+        let batteries = data.get("batteries").and_then(Value::as_list).unwrap_or_default();
+        batteries
+            .iter()
+            .map(|battery| paint_info(&describe(battery, batteries.len() > 1), cfg))
+            .collect()
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["name", "percent", "status", "time_remaining", "health", "cycle_count"]
+    }
+
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        list_items(data, "batteries")
+            .into_iter()
+            .map(|mut battery| {
+                let left = format_time_remaining(&battery).unwrap_or_default();
+                battery.set("time_remaining", left);
+                battery
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn fake_power_supply(name: &str) -> std::path::PathBuf {
+        let root = env::temp_dir().join(format!("hostfetch-battery-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let supplies: &[(&str, &[(&str, &str)])] = &[
+            (
+                "BAT0",
+                &[
+                    ("type", "Battery"),
+                    ("status", "Discharging"),
+                    ("charge_now", "2000000"),
+                    ("charge_full", "4000000"),
+                    ("charge_full_design", "5000000"),
+                    ("current_now", "1000000"),
+                    ("voltage_min_design", "11100000"),
+                ],
+            ),
+            (
+                "BAT1",
+                &[
+                    ("type", "Battery"),
+                    ("status", "Charging"),
+                    ("capacity", "75"),
+                    ("energy_now", "30000000"),
+                    ("energy_full", "40000000"),
+                    ("energy_full_design", "50000000"),
+                    ("power_now", "5000000"),
+                    ("cycle_count", "120"),
+                ],
+            ),
+            ("BAT2", &[("type", "Battery"), ("present", "0"), ("capacity", "0")]),
+            ("AC", &[("type", "Mains"), ("online", "1")]),
+            ("hidpp_battery_0", &[("type", "Battery"), ("scope", "Device"), ("capacity", "90")]),
+        ];
+        for (name, files) in supplies {
+            let dir = root.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            for (file, value) in *files {
+                fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+            }
+        }
+        root
+    }
+
+    #[test]
+    fn reads_system_batteries_only() {
+        let root = fake_power_supply("read");
+        let batteries = read_batteries(&root);
+        fs::remove_dir_all(root).unwrap();
+
+        let names: Vec<&str> = batteries.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["BAT0", "BAT1"]);
+
+        // Charge in µAh times the design voltage gives energy in µWh.
+        let bat0 = &batteries[0];
+        assert_eq!(bat0.energy_now, Some(22_200_000));
+        assert_eq!(bat0.energy_full, Some(44_400_000));
+        assert_eq!(bat0.power_now, Some(11_100_000));
+        assert_eq!(bat0.capacity, 50.0);
+        assert_eq!(bat0.health(), Some(80.0));
+        assert_eq!(bat0.time_remaining(), Some(2 * 3600));
+
+        let bat1 = &batteries[1];
+        assert_eq!(bat1.capacity, 75.0);
+        assert_eq!(bat1.cycle_count, Some(120));
+        assert_eq!(bat1.time_remaining(), Some(2 * 3600));
+    }
+
+    #[test]
+    fn combines_packs() {
+        let root = fake_power_supply("combine");
+        let batteries = read_batteries(&root);
+        fs::remove_dir_all(root).unwrap();
+
+        let total = combine(&batteries).unwrap();
+        assert_eq!(total.name, "total");
+        assert_eq!(total.status, "Charging");
+        assert_eq!(total.energy_now, Some(52_200_000));
+        assert_eq!(total.energy_full, Some(84_400_000));
+        assert_eq!(total.energy_full_design, Some(105_500_000));
+        assert_eq!(total.power_now, Some(16_100_000));
+        assert_eq!(total.cycle_count, Some(120));
+        assert!((total.capacity - 52.2 / 84.4 * 100.0).abs() < 1e-9);
+
+        assert!(combine(&[]).is_none());
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{ImDocument, Item, Key, Table, TableLike, Value};
+
+use crate::config::{self, Config, Position, COLORS, STYLES};
+use crate::module::{self, Registry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub location: Option<(usize, usize)>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match self.location {
+            Some((line, column)) => write!(
+                f,
+                "{}:{}:{}: {}: {}",
+                self.path.display(),
+                line,
+                column,
+                severity,
+                self.message
+            ),
+            None => write!(f, "{}: {}: {}", self.path.display(), severity, self.message),
+        }
+    }
+}
+
+const COLOR_KEYS: &[(&str, &str)] = &[
+    ("host", "color"),
+    ("info", "name_color"),
+    ("info", "info_color"),
+    ("info", "border_color"),
+    ("icons", "color"),
+];
+
+const STYLE_KEYS: &[(&str, &str)] = &[
+    ("host", "styles"),
+    ("info", "name_styles"),
+    ("info", "info_styles"),
+];
+
+// Tables keyed by module id rather than by setting name.
+const MODULE_TABLES: &[&str] = &["modules.timeouts", "modules.formats"];
+
+pub fn parse_error(path: &Path, content: &str, error: &toml::de::Error) -> Diagnostic {
+    Diagnostic {
+        path: path.to_path_buf(),
+        location: error.span().map(|span| location(content, span.start)),
+        severity: Severity::Error,
+        message: error.message().trim().to_string(),
+    }
+}
+
+pub fn check_file(path: &Path, registry: &Registry) -> io::Result<Vec<Diagnostic>> {
+    let content = fs::read_to_string(path)?;
+    Ok(check(path, &content, registry))
+}
+
+// Problems serde would either reject (reported as errors) or silently
+// paper over, such as unknown keys or a colour that falls back to white.
+pub fn check(path: &Path, content: &str, registry: &Registry) -> Vec<Diagnostic> {
+    let module_ids: Vec<&str> = registry.iter().map(|module| module.id()).collect();
+    let mut checker = Checker {
+        path,
+        content,
+        registry,
+        module_ids: &module_ids,
+        diagnostics: Vec::new(),
+    };
+
+    if let Err(e) = toml::from_str::<Config>(content) {
+        checker.diagnostics.push(parse_error(path, content, &e));
+    }
+
+    if let Ok(document) = ImDocument::parse(content) {
+        let root = document.as_table();
+        checker.check_keys(root, &schema(), "");
+        checker.check_colors(root);
+        checker.check_styles(root);
+        checker.check_order(root);
+        checker.check_formats(root);
+        checker.check_position(root);
+    }
+
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.location);
+    diagnostics
+}
+
+fn schema() -> toml::Table {
+    let mut schema = match toml::Value::try_from(Config::default()) {
+        Ok(toml::Value::Table(table)) => table,
+        _ => toml::Table::new(),
+    };
+    if let Ok(position) = toml::Value::try_from(Position::default()) {
+        schema.insert("position".to_string(), position);
+    }
+    schema
+}
+
+struct Checker<'a> {
+    path: &'a Path,
+    content: &'a str,
+    registry: &'a Registry,
+    module_ids: &'a [&'a str],
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn warn(&mut self, span: Option<Range<usize>>, message: String) {
+        self.diagnostics.push(Diagnostic {
+            path: self.path.to_path_buf(),
+            location: span.map(|span| location(self.content, span.start)),
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    fn check_keys(&mut self, table: &dyn TableLike, schema: &toml::Table, section: &str) {
+        for (key, item) in table.iter() {
+            let span = table.key(key).and_then(Key::span);
+            let name = match section {
+                "" => key.to_string(),
+                _ => format!("{}.{}", section, key),
+            };
+
+            if MODULE_TABLES.contains(&section) {
+                if !self.module_ids.contains(&key) {
+                    let hint = suggest(key, self.module_ids);
+                    self.warn(span, format!("unknown module '{}' in [{}]{}", key, section, hint));
+                }
+                continue;
+            }
+
+            match schema.get(key) {
+                Some(toml::Value::Table(inner)) => {
+                    if let Some(table) = item.as_table_like() {
+                        self.check_keys(table, inner, &name);
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    let known: Vec<&str> = schema.keys().map(String::as_str).collect();
+                    let hint = suggest(key, &known);
+                    match section {
+                        "" => self.warn(span, format!("unknown key '{}'{}", key, hint)),
+                        _ => self.warn(span, format!("unknown key '{}' in [{}]{}", key, section, hint)),
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_colors(&mut self, root: &Table) {
+        for (section, key) in COLOR_KEYS {
+            let Some(value) = lookup(root, section, key) else {
+                continue;
+            };
+            if let Some(color) = value.as_str().filter(|color| !config::is_color(color)) {
+                let hint = suggest(&color.to_lowercase(), COLORS);
+                self.warn(
+                    value.span(),
+                    format!("invalid colour '{}' for {}.{}{}", color, section, key, hint),
+                );
+            }
+        }
+    }
+
+    fn check_styles(&mut self, root: &Table) {
+        for (section, key) in STYLE_KEYS {
+            let Some(styles) = lookup(root, section, key).and_then(Value::as_array) else {
+                continue;
+            };
+            for value in styles {
+                let Some(style) = value.as_str() else {
+                    continue;
+                };
+                if !STYLES.contains(&style.to_lowercase().as_str()) {
+                    let hint = suggest(&style.to_lowercase(), STYLES);
+                    self.warn(
+                        value.span(),
+                        format!("unknown style '{}' in {}.{}{}", style, section, key, hint),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_order(&mut self, root: &Table) {
+        let Some(order) = lookup(root, "modules", "order").and_then(Value::as_array) else {
+            return;
+        };
+        let mut seen = Vec::new();
+        for value in order {
+            let Some(id) = value.as_str() else {
+                continue;
+            };
+            if !self.module_ids.contains(&id) {
+                let hint = suggest(id, self.module_ids);
+                self.warn(value.span(), format!("unknown module '{}' in modules.order{}", id, hint));
+            } else if seen.contains(&id) {
+                self.warn(value.span(), format!("module '{}' is listed twice in modules.order", id));
+            }
+            seen.push(id);
+        }
+    }
+
+    // Unknown modules are already reported by check_keys.
+    fn check_formats(&mut self, root: &Table) {
+        let registry = self.registry;
+        let Some(formats) = root
+            .get("modules")
+            .and_then(Item::as_table_like)
+            .and_then(|modules| modules.get("formats"))
+            .and_then(Item::as_table_like)
+        else {
+            return;
+        };
+
+        for (id, item) in formats.iter() {
+            let (Some(module), Some(value)) = (registry.get(id), item.as_value()) else {
+                continue;
+            };
+            if let Some(problem) = value
+                .as_str()
+                .and_then(|format| module::format_problem(module, format))
+            {
+                self.warn(value.span(), format!("format for {}: {}", id, problem));
+            }
+        }
+    }
+
+    // An order of 0 hides the module, so only positive values can clash.
+    fn check_position(&mut self, root: &Table) {
+        let Some(position) = root.get("position").and_then(|item| item.as_table_like()) else {
+            return;
+        };
+        let mut seen: HashMap<i64, &str> = HashMap::new();
+        for (key, item) in position.iter() {
+            let Some(order) = item.as_integer().filter(|&order| order > 0) else {
+                continue;
+            };
+            match seen.get(&order) {
+                Some(first) => {
+                    let message = format!(
+                        "position.{} uses order {} already taken by position.{}",
+                        key, order, first
+                    );
+                    self.warn(item.span(), message);
+                }
+                None => {
+                    seen.insert(order, key);
+                }
+            }
+        }
+    }
+}
+
+fn lookup<'a>(root: &'a Table, section: &str, key: &str) -> Option<&'a Value> {
+    root.get(section)?.as_table_like()?.get(key)?.as_value()
+}
+
+fn location(content: &str, offset: usize) -> (usize, usize) {
+    let before = content.get(..offset).unwrap_or(content);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+fn suggest(word: &str, candidates: &[&str]) -> String {
+    candidates
+        .iter()
+        .map(|candidate| (distance(word, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!(" (did you mean '{}'?)", candidate))
+        .unwrap_or_default()
+}
+
+// Edit distance counting a swap of two neighbouring letters as one edit,
+// since "magneta" is a far more likely typo than two substitutions.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swapped_letters_count_as_one_edit() {
+        assert_eq!(distance("magneta", "magenta"), 1);
+        assert_eq!(distance("blod", "bold"), 1);
+        assert_eq!(distance("red", "red"), 0);
+        assert_eq!(distance("", "red"), 3);
+        assert_eq!(distance("colour", "color"), 1);
+    }
+
+    #[test]
+    fn suggests_only_close_candidates() {
+        assert_eq!(suggest("magneta", COLORS), " (did you mean 'magenta'?)");
+        assert_eq!(suggest("itlaic", STYLES), " (did you mean 'italic'?)");
+        // Two edits are too many for a four-letter word.
+        assert_eq!(suggest("bxlx", STYLES), "");
+        assert_eq!(suggest("purple", COLORS), "");
+    }
+
+    #[test]
+    fn reports_problems_with_their_location() {
+        let content = "[host]\ncolor = \"magneta\"\n\n[modules.formats]\nram = \"{used} {bogus}\"\n";
+        let registry = Registry::builtin(&Config::default());
+        let messages: Vec<String> = check(Path::new("config.toml"), content, &registry)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            messages,
+            [
+                "config.toml:2:9: warning: invalid colour 'magneta' for host.color (did you mean 'magenta'?)",
+                "config.toml:5:7: warning: format for ram: unknown placeholder {bogus} (available: used, total, percent)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "A small program that displays host information")]
+pub struct Cli {
+    /// Read configuration from this file instead of the default location
+    /// (also taken from $HOSTFETCH_CONFIG)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Disable icons
+    #[arg(long)]
+    pub no_icons: bool,
+
+    /// Show only these modules, in this order
+    #[arg(long, value_delimiter = ',', value_name = "MODULES")]
+    pub only: Vec<String>,
+
+    /// Hide these modules
+    #[arg(long, value_delimiter = ',', value_name = "MODULES")]
+    pub hide: Vec<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Shorthand for --format json
+    #[arg(long, conflicts_with = "format")]
+    pub json: bool,
+
+    /// Exit with a non-zero status if any module fails
+    #[arg(long)]
+    pub strict: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// List available modules
+    Modules,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print the path of the configuration file
+    Path,
+    /// Print the effective configuration
+    Show,
+    /// Report unknown keys, invalid colours and other mistakes
+    Check,
+    /// Write a commented config file with the default settings
+    Init {
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the effective value of a key such as info.info_color
+    Get { key: String },
+    /// Set a key in the user's config file, keeping its comments
+    Set { key: String, value: String },
+    /// Remove a key from the user's config file
+    Unset { key: String },
+    /// Print every effective setting as key = value
+    List,
+}
+
+impl Cli {
+    pub fn format(&self) -> Format {
+        if self.json {
+            Format::Json
+        } else {
+            self.format
+        }
+    }
+}
@@ -1,10 +1,39 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
 use std::fs;
-use dirs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::check;
 use colored::ColoredString;
 use colored::Colorize;
 use colored::Color;
 
+pub const STYLES: &[&str] = &["bold", "italic", "underline", "dimmed", "blink", "reverse"];
+
+pub const COLORS: &[&str] = &[
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+];
+
+pub fn is_color(color: &str) -> bool {
+    Config::parse_hex(color).is_some() || COLORS.contains(&color.to_lowercase().as_str())
+}
+
 pub trait Stylize {
     fn style(self, styles: &[String]) -> ColoredString;
 }
@@ -26,22 +55,130 @@ impl Stylize for ColoredString {
     }
 }
 
+// Every table and key may be left out; missing ones take their value from
+// Config::default(). `modules` and `position` stay None when absent so an
+// old [position] table is still honoured.
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
+    pub logo: LogoSource,
+    pub logo_position: LogoPosition,
+    pub logo_width: usize,
     pub host: HostStyle,
-    pub position: Position,
+    #[serde(default)]
+    pub modules: Option<Modules>,
+    #[serde(default, skip_serializing)]
+    pub position: Option<Position>,
     pub info: InfoStyle,
     pub icons: IconStyle,
+    pub uptime: UptimeConfig,
+    pub cpu: CpuConfig,
+    pub disk: DiskConfig,
+    pub network: NetworkConfig,
+    pub battery: BatteryConfig,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum LogoSource {
+    #[default]
+    Auto,
+    None,
+    Named(String),
+    File(PathBuf),
+}
+
+impl From<String> for LogoSource {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "auto" => LogoSource::Auto,
+            "none" => LogoSource::None,
+            _ if value.contains('/') => match value.strip_prefix("~/").zip(dirs::home_dir()) {
+                Some((rest, home)) => LogoSource::File(home.join(rest)),
+                None => LogoSource::File(PathBuf::from(value)),
+            },
+            _ => LogoSource::Named(value.to_lowercase()),
+        }
+    }
+}
+
+impl From<LogoSource> for String {
+    fn from(source: LogoSource) -> Self {
+        match source {
+            LogoSource::Auto => "auto".to_string(),
+            LogoSource::None => "none".to_string(),
+            LogoSource::Named(name) => name,
+            LogoSource::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+fn default_logo_width() -> usize {
+    24
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogoPosition {
+    #[default]
+    Left,
+    Top,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HostStyle {
     pub color: String,
-    #[serde(default)]
     pub styles: Vec<String>,
 }
 
+impl Default for HostStyle {
+    fn default() -> Self {
+        Self {
+            color: "magenta".into(),
+            styles: vec!["bold".into()],
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Modules {
+    pub order: Vec<String>,
+    pub timeout_ms: u64,
+    pub timeouts: HashMap<String, u64>,
+    pub on_error: OnError,
+    pub formats: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    Hide,
+    #[default]
+    Na,
+    Error,
+}
+
+fn default_timeout_ms() -> u64 {
+    1000
+}
+
+impl Default for Modules {
+    fn default() -> Self {
+        Self {
+            order: Config::default_order(),
+            timeout_ms: default_timeout_ms(),
+            timeouts: HashMap::new(),
+            on_error: OnError::default(),
+            formats: HashMap::new(),
+        }
+    }
+}
+
+// Numeric ordering used by configs written before [modules] existed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Position {
     pub os_order: u8,
     pub host_order: u8,
@@ -55,30 +192,259 @@ pub struct Position {
     pub locale_order: u8,
 }
 
+impl Default for Position {
+    fn default() -> Self {
+        Self {
+            os_order: 1,
+            host_order: 2,
+            terminal_order: 3,
+            shell_order: 4,
+            kernel_order: 5,
+            uptime_order: 6,
+            load_average_order: 7,
+            ram_order: 8,
+            swap_order: 9,
+            locale_order: 10,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InfoStyle {
     pub names_enabled: bool,
     pub name_color: String,
-    #[serde(default)]
     pub name_styles: Vec<String>,
     pub info_color: String,
-    #[serde(default)]
     pub info_styles: Vec<String>,
     pub border_color: String,
+    pub label_align: LabelAlign,
+}
+
+impl Default for InfoStyle {
+    fn default() -> Self {
+        Self {
+            names_enabled: true,
+            name_color: "white".into(),
+            name_styles: vec!["italic".into()],
+            info_color: "blue".into(),
+            info_styles: vec!["bold".into()],
+            border_color: "blue".into(),
+            label_align: LabelAlign::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelAlign {
+    #[default]
+    Left,
+    Right,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct IconStyle {
     pub enabled: bool,
     pub color: String,
+    pub width: usize,
+}
+
+impl Default for IconStyle {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            color: "green".into(),
+            width: 1,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UptimeConfig {
+    pub style: UptimeStyle,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UptimeStyle {
+    #[default]
+    Long,
+    Short,
+    Clock,
+    Since,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CpuConfig {
+    pub usage: bool,
+    pub sample_ms: u64,
+}
+
+impl Default for CpuConfig {
+    fn default() -> Self {
+        Self {
+            usage: false,
+            sample_ms: 200,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiskConfig {
+    pub mounts: Vec<String>,
+    pub hide_fs: Vec<String>,
+}
+
+fn default_hide_fs() -> Vec<String> {
+    [
+        "tmpfs", "devtmpfs", "overlay", "squashfs", "proc", "sysfs", "devpts", "cgroup",
+        "cgroup2", "mqueue", "debugfs", "tracefs", "securityfs", "pstore", "bpf", "configfs",
+        "fusectl", "hugetlbfs", "autofs", "binfmt_misc", "efivarfs", "nsfs", "ramfs",
+        "rpc_pipefs", "selinuxfs",
+    ]
+    .iter()
+    .map(|fs| fs.to_string())
+    .collect()
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        Self {
+            mounts: vec!["/".to_string()],
+            hide_fs: default_hide_fs(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub hide: Vec<String>,
+}
+
+fn default_network_hide() -> Vec<String> {
+    ["lo", "docker*", "br-*", "veth*", "virbr*"]
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            hide: default_network_hide(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatteryConfig {
+    pub combine: bool,
+}
+
+impl Position {
+    fn order(&self) -> Vec<String> {
+        let mut items = vec![
+            (self.host_order, "host"),
+            (self.os_order, "os"),
+            (self.terminal_order, "terminal"),
+            (self.shell_order, "shell"),
+            (self.kernel_order, "kernel"),
+            (self.uptime_order, "uptime"),
+            (self.load_average_order, "load_average"),
+            (self.ram_order, "ram"),
+            (self.swap_order, "swap"),
+            (self.locale_order, "locale"),
+        ];
+
+        items.retain(|(order, _)| *order > 0);
+        items.sort_by_key(|(order, _)| *order);
+
+        items.into_iter().map(|(_, id)| id.to_string()).collect()
+    }
 }
 
 impl Config {
 
+    pub fn module_order(&self) -> Vec<String> {
+        match (&self.modules, &self.position) {
+            (Some(modules), _) => modules.order.clone(),
+            (None, Some(position)) => position.order(),
+            (None, None) => Self::default_order(),
+        }
+    }
+
+    pub fn set_module_order(&mut self, order: Vec<String>) {
+        self.modules.get_or_insert_with(Modules::default).order = order;
+        self.position = None;
+    }
+
+    pub fn on_error(&self) -> OnError {
+        self.modules
+            .as_ref()
+            .map(|modules| modules.on_error)
+            .unwrap_or_default()
+    }
+
+    pub fn module_timeout(&self, id: &str) -> Duration {
+        let ms = match &self.modules {
+            Some(modules) => modules.timeouts.get(id).copied().unwrap_or(modules.timeout_ms),
+            None => default_timeout_ms(),
+        };
+        Duration::from_millis(ms)
+    }
+
+    pub fn module_format(&self, id: &str) -> Option<&str> {
+        self.modules.as_ref()?.formats.get(id).map(String::as_str)
+    }
+
+    pub fn module_formats(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.modules.iter().flat_map(|modules| &modules.formats)
+    }
+
+    fn default_order() -> Vec<String> {
+        [
+            "os",
+            "host",
+            "desktop",
+            "terminal",
+            "shell",
+            "kernel",
+            "packages",
+            "uptime",
+            "load_average",
+            "cpu",
+            "gpu",
+            "ram",
+            "swap",
+            "disk",
+            "network",
+            "battery",
+            "locale",
+        ]
+        .iter()
+        .map(|id| id.to_string())
+        .collect()
+    }
+
     pub fn names_enabled(&self) -> bool {
         self.info.names_enabled
     }
 
+    pub fn icon_width(&self) -> usize {
+        self.icons.width.clamp(1, 2)
+    }
+
+    pub fn label_align(&self) -> LabelAlign {
+        self.info.label_align
+    }
+
     pub fn get_name_color(&self) -> Color {
         self.parse_color(&self.info.name_color)
     }
@@ -115,6 +481,28 @@ impl Config {
         self.parse_color(&self.info.border_color)
     }
 
+    pub fn logo(&self) -> &LogoSource {
+        &self.logo
+    }
+
+    pub fn logo_position(&self) -> LogoPosition {
+        self.logo_position
+    }
+
+    pub fn logo_width(&self) -> usize {
+        self.logo_width
+    }
+
+    pub fn uptime_style(&self) -> UptimeStyle {
+        self.uptime.style
+    }
+
+    pub fn cpu_usage_interval(&self) -> Option<Duration> {
+        self.cpu
+            .usage
+            .then(|| Duration::from_millis(self.cpu.sample_ms))
+    }
+
     fn parse_color(&self, color_str: &str) -> Color {
         if let Some(rgb) = Self::parse_hex(color_str) {
             return Color::TrueColor { r: rgb.0, g: rgb.1, b: rgb.2 };
@@ -143,7 +531,10 @@ impl Config {
 
     fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
         let hex = hex.trim_start_matches('#');
-        
+        if !hex.is_ascii() {
+            return None;
+        }
+
         match hex.len() {
             3 => {
                 let r = u8::from_str_radix(&hex[0..1], 16).ok()?;
@@ -165,84 +556,270 @@ impl Config {
 impl Default for Config {
     fn default() -> Self {
         Self {
-            host: HostStyle {
-                color: "magenta".into(),
-                styles: vec!["bold".into()],
-            },
-            position: Position {
-                os_order: 1,
-                host_order: 2,
-                terminal_order: 3,
-                shell_order: 4,
-                kernel_order: 5,
-                uptime_order: 6,
-                load_average_order: 7,
-                ram_order: 8,
-                swap_order: 9,
-                locale_order: 10,
-            },
-            info: InfoStyle {
-                names_enabled: true,
-                name_color: "white".into(),
-                name_styles: vec!["italic".into()],
-                info_color: "blue".into(),
-                info_styles: vec!["bold".into()],
-                border_color: "blue".into(),
-            },
-            icons: IconStyle {
-                color: "green".into(),
-                enabled: true,
+            logo: LogoSource::default(),
+            logo_position: LogoPosition::default(),
+            logo_width: default_logo_width(),
+            host: HostStyle::default(),
+            modules: Some(Modules::default()),
+            position: None,
+            info: InfoStyle::default(),
+            icons: IconStyle::default(),
+            uptime: UptimeConfig::default(),
+            cpu: CpuConfig::default(),
+            disk: DiskConfig::default(),
+            network: NetworkConfig::default(),
+            battery: BatteryConfig::default(),
+        }
+    }
+}
+
+// Admin-provided defaults, read before the user's file.
+pub const SYSTEM_CONFIG: &str = "/etc/hostfetch/config.toml";
+
+// --config, then $HOSTFETCH_CONFIG; both must exist when given.
+fn explicit_path(cli_path: Option<&Path>) -> Option<PathBuf> {
+    cli_path.map(Path::to_path_buf).or_else(|| {
+        env::var_os("HOSTFETCH_CONFIG")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+fn default_paths() -> Vec<PathBuf> {
+    let xdg = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute());
+    let home = dirs::home_dir().map(|home| home.join(".config"));
+
+    xdg.into_iter()
+        .chain(home)
+        .map(|dir| dir.join("hostfetch").join("config.toml"))
+        .collect()
+}
+
+// The user's config file: an explicit path, else the first default location
+// that exists, else the preferred one for creating it.
+pub fn config_path(cli_path: Option<&Path>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if let Some(path) = explicit_path(cli_path) {
+        return Ok(path);
+    }
+    let paths = default_paths();
+    let path = paths.iter().find(|path| path.is_file()).or(paths.first());
+    Ok(path.ok_or("Home directory not found")?.clone())
+}
+
+// Every file that makes up the config, lowest layer first.
+pub fn config_files(cli_path: Option<&Path>) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    let system = PathBuf::from(SYSTEM_CONFIG);
+    if system.is_file() {
+        files.push(system);
+    }
+    let user = config_path(cli_path)?;
+    if user.is_file() || explicit_path(cli_path).is_some() {
+        files.push(user);
+    }
+    Ok(files)
+}
+
+// Later files override earlier ones key by key, so a user file only needs
+// the settings it changes from the system one.
+pub fn load(paths: &[PathBuf]) -> Result<Config, Box<dyn std::error::Error>> {
+    let mut merged = toml::Table::new();
+    for path in paths {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        let layer: toml::Table = toml::from_str::<Config>(&content)
+            .and_then(|_| toml::from_str(&content))
+            .map_err(|e| check::parse_error(path, &content, &e).to_string())?;
+        merge(&mut merged, layer);
+    }
+    let mut config: Config = toml::Value::Table(merged).try_into()?;
+    // Spell out the [modules] defaults so `config show` and `config list`
+    // include them, unless the legacy [position] table decides the order.
+    if config.modules.is_none() && config.position.is_none() {
+        config.modules = Some(Modules::default());
+    }
+    Ok(config)
+}
+
+fn merge(base: &mut toml::Table, layer: toml::Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(value)) => merge(base, value),
+            (_, value) => {
+                base.insert(key, value);
             }
         }
     }
 }
 
-pub fn load_or_create() -> Result<Config, Box<dyn std::error::Error>> {
-    let config_path = dirs::home_dir()
-        .ok_or("Home directory not found")?
-        .join(".config")
-        .join("hostfetch")
-        .join("config.toml");
+// With no config file at all the built-in defaults apply; nothing is
+// written until `config init` is run.
+pub fn load_config(cli_path: Option<&Path>) -> Result<Config, Box<dyn std::error::Error>> {
+    let files = config_files(cli_path)?;
+    if files.is_empty() {
+        return Ok(Config::default());
+    }
+    load(&files)
+}
 
-    if let Some(parent) = config_path.parent() {
+// Comments `config init` writes above a key or table header.
+const TEMPLATE_COMMENTS: &[(&str, &str)] = &[
+    (
+        "logo",
+        "\"auto\" picks a logo from /etc/os-release; also a logo name such as\n\
+         \"arch\", \"none\", or the path to a text file using ${c1}..${c6} colours.\n\
+         A .png path is drawn as an image in kitty, iTerm2 and sixel terminals.",
+    ),
+    ("logo_position", "\"left\" of the info boxes or \"top\""),
+    ("logo_width", "columns taken by a .png logo"),
+    ("modules.order", "remove an entry to hide it"),
+    ("modules.timeout_ms", "milliseconds to wait for a module before showing \"timeout\""),
+    ("modules.on_error", "what a failing module shows: \"na\", \"error\" (the message) or \"hide\""),
+    (
+        "modules.timeouts",
+        "per-module overrides, e.g. for a slow getprop on Android:\n  host = 3000",
+    ),
+    (
+        "modules.formats",
+        "per-module line templates; `hostfetch modules` lists each module's fields:\n  \
+         ram = \"{used} / {total} [{percent}]\"\n  \
+         kernel = \"{name} {release} ({arch})\"",
+    ),
+    (
+        "info.label_align",
+        "labels flush \"left\" (values line up after the padding) or \"right\"",
+    ),
+    (
+        "icons.width",
+        "cells a Nerd Font icon takes: 1, or 2 if the boxes' right edge is ragged",
+    ),
+    (
+        "uptime.style",
+        "long (\"3 days, 4 hours\"), short (\"3d 4h 12m\"), clock (\"76:12\")\n\
+         or since (\"since 2026-10-15 08:00\")",
+    ),
+    ("cpu.usage", "sample overall usage for sample_ms milliseconds before printing"),
+    (
+        "disk.mounts",
+        "mount points to show; \"*\" adds every mount whose type is not in hide_fs",
+    ),
+    ("network.hide", "interfaces to leave out; \"*\" matches any run of characters"),
+    (
+        "battery.combine",
+        "show one line summed over all batteries instead of one per battery",
+    ),
+];
+
+// The values come from Config::default(), so the template cannot drift
+// from what hostfetch uses when a key is missing.
+pub fn template() -> String {
+    let defaults = toml::to_string_pretty(&Config::default())
+        .expect("the default config serializes");
+    let mut document: toml_edit::DocumentMut =
+        defaults.parse().expect("serialized toml parses");
+
+    for (path, text) in TEMPLATE_COMMENTS {
+        let comment: String = text.lines().map(|line| format!("# {}\n", line)).collect();
+        let (section, key) = path.rsplit_once('.').unwrap_or(("", path));
+        let table = section
+            .split('.')
+            .filter(|name| !name.is_empty())
+            .try_fold(document.as_table_mut(), |table, name| {
+                table.get_mut(name)?.as_table_mut()
+            });
+        let Some(table) = table else {
+            continue;
+        };
+
+        if let Some(header) = table.get_mut(key).and_then(toml_edit::Item::as_table_mut) {
+            header.decor_mut().set_prefix(format!("\n{}", comment));
+        } else if let Some(mut key) = table.key_mut(key) {
+            key.leaf_decor_mut().set_prefix(comment);
+        }
+    }
+
+    format!(
+        "# hostfetch configuration. Every key is optional; a missing one keeps\n\
+         # the default shown here.\n\n{}",
+        document
+    )
+}
+
+pub fn init(path: &Path, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if path.exists() && !force {
+        return Err(format!("{} already exists; use --force to overwrite it", path.display()).into());
+    }
+    if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
+    fs::write(path, template())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn template_matches_the_defaults() {
+        let template = template();
+        let parsed: Config = toml::from_str(&template).unwrap();
+        assert_eq!(
+            toml::to_string(&parsed).unwrap(),
+            toml::to_string(&Config::default()).unwrap()
+        );
+
+        for (path, text) in TEMPLATE_COMMENTS {
+            let comment: String = text.lines().map(|line| format!("# {}\n", line)).collect();
+            let key = path.rsplit('.').next().unwrap();
+            assert!(
+                template.contains(&format!("{}{} =", comment, key))
+                    || template.contains(&format!("{}[{}]", comment, path)),
+                "no comment above {}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn merges_tables_and_replaces_other_values() {
+        let mut base: toml::Table = toml::from_str(
+            "logo = \"arch\"\n[info]\ninfo_color = \"red\"\nname_color = \"white\"\n\
+             [modules]\norder = [\"os\", \"host\"]\n[modules.timeouts]\nhost = 3000\n",
+        )
+        .unwrap();
+        let layer: toml::Table = toml::from_str(
+            "[info]\ninfo_color = \"blue\"\n[modules]\norder = [\"ram\"]\n[modules.timeouts]\ncpu = 500\n",
+        )
+        .unwrap();
+        merge(&mut base, layer);
 
-    if !config_path.exists() {
-        let toml_content = r#"
-[host]
-color = "magenta"
-styles = ["bold"]
-
-[position]
-# set 0 to hide element
-os_order = 1
-host_order = 2
-terminal_order = 3
-shell_order = 4
-kernel_order = 5
-uptime_order = 6
-load_average_order = 7
-ram_order = 8
-swap_order = 9
-locale_order = 10
-
-[info]
-names_enabled = true
-name_color = "white"
-name_styles = ["italic"]
-info_color = "blue"
-info_styles = ["bold"]
-border_color = "blue"
-
-[icons]
-enabled = true
-color = "green"
-"#;
-
-        fs::write(&config_path, toml_content)?;
-    }
-
-    let content = fs::read_to_string(config_path)?;
-    Ok(toml::from_str(&content)?)
-}
\ No newline at end of file
+        let expected: toml::Table = toml::from_str(
+            "logo = \"arch\"\n[info]\ninfo_color = \"blue\"\nname_color = \"white\"\n\
+             [modules]\norder = [\"ram\"]\n[modules.timeouts]\nhost = 3000\ncpu = 500\n",
+        )
+        .unwrap();
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn user_file_overrides_system_file() {
+        let dir = env::temp_dir().join(format!("hostfetch-layers-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let system = dir.join("system.toml");
+        let user = dir.join("user.toml");
+        fs::write(&system, "[host]\ncolor = \"red\"\n[info]\nborder_color = \"yellow\"\n").unwrap();
+        fs::write(&user, "[info]\nborder_color = \"cyan\"\n").unwrap();
+
+        let config = load(&[system, user]).unwrap();
+        assert_eq!(config.host.color, "red");
+        assert_eq!(config.host.styles, ["bold"]);
+        assert_eq!(config.info.border_color, "cyan");
+        assert_eq!(config.info.info_color, "blue");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,377 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError, Value};
+
+const SYSFS_CPU: &str = "/sys/devices/system/cpu";
+
+#[derive(Debug, Clone)]
+pub struct CpuCluster {
+    pub threads: usize,
+    pub cur_freq_mhz: Option<u64>,
+    pub max_freq_mhz: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CpuInfo {
+    pub model: String,
+    pub cores: usize,
+    pub threads: usize,
+    pub clusters: Vec<CpuCluster>,
+}
+
+pub fn get_cpu_info() -> Option<CpuInfo> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    let sysfs = Path::new(SYSFS_CPU);
+
+    let mut cpus = read_sys(&sysfs.join("online"))
+        .map(|list| parse_cpu_list(&list))
+        .unwrap_or_default();
+    if cpus.is_empty() {
+        cpus = (0..cpuinfo.lines().filter(|l| l.starts_with("processor")).count()).collect();
+    }
+
+    let threads = cpus.len().max(1);
+    let cores = count_cores(sysfs, &cpus).unwrap_or(threads);
+
+    let mut clusters = read_clusters(sysfs);
+    if clusters.is_empty() {
+        clusters.push(CpuCluster {
+            threads,
+            cur_freq_mhz: cpuinfo_field(&cpuinfo, "cpu MHz")
+                .and_then(|mhz| mhz.parse::<f64>().ok())
+                .map(|mhz| mhz as u64),
+            max_freq_mhz: None,
+        });
+    }
+
+    Some(CpuInfo {
+        model: parse_model(&cpuinfo).unwrap_or_else(|| "Unknown".to_string()),
+        cores,
+        threads,
+        clusters,
+    })
+}
+
+pub fn sample_usage(interval: Duration) -> Option<f64> {
+    let (idle_before, total_before) = read_cpu_times()?;
+    thread::sleep(interval);
+    let (idle_after, total_after) = read_cpu_times()?;
+
+    let total = total_after.saturating_sub(total_before);
+    if total == 0 {
+        return None;
+    }
+
+    let idle = idle_after.saturating_sub(idle_before);
+    Some((1.0 - idle as f64 / total as f64) * 100.0)
+}
+
+fn read_cpu_times() -> Option<(u64, u64)> {
+    let stat = fs::read_to_string("/proc/stat").ok()?;
+    let times: Vec<u64> = stat
+        .lines()
+        .next()?
+        .strip_prefix("cpu ")?
+        .split_whitespace()
+        .filter_map(|s| s.parse().ok())
+        .collect();
+
+    // idle + iowait
+    let idle = times.get(3)? + times.get(4).unwrap_or(&0);
+    Some((idle, times.iter().sum()))
+}
+
+fn parse_model(cpuinfo: &str) -> Option<String> {
+    let model = ["model name", "Hardware", "Processor", "cpu model"]
+        .iter()
+        .find_map(|key| cpuinfo_field(cpuinfo, key))?;
+
+    let model = model
+        .replace("(R)", "")
+        .replace("(TM)", "")
+        .replace("(tm)", "");
+    let model = match model.find(" @ ") {
+        Some(index) => &model[..index],
+        None => &model,
+    };
+
+    Some(model.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn cpuinfo_field(cpuinfo: &str, key: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => cpus.extend(part.parse::<usize>().ok()),
+        }
+    }
+    cpus
+}
+
+// core_id restarts in every cluster on arm64 device-tree kernels, where
+// the package id is 0 for all of them, so cluster_id is part of the key
+// when the kernel has it.
+fn count_cores(sysfs: &Path, cpus: &[usize]) -> Option<usize> {
+    let mut cores = BTreeSet::new();
+    for cpu in cpus {
+        let topology = sysfs.join(format!("cpu{}", cpu)).join("topology");
+        let package = read_sys(&topology.join("physical_package_id"))?;
+        let cluster = read_sys(&topology.join("cluster_id"));
+        let core = read_sys(&topology.join("core_id"))?;
+        cores.insert((package, cluster, core));
+    }
+    (!cores.is_empty()).then_some(cores.len())
+}
+
+// Each cpufreq policy covers a group of CPUs sharing a clock. Policies with
+// the same maximum frequency are merged, which leaves one entry per cluster
+// on big.LITTLE parts and a single entry on homogeneous ones. Only online
+// CPUs are counted, matching the thread count from cpu/online.
+fn read_clusters(sysfs: &Path) -> Vec<CpuCluster> {
+    let Ok(entries) = fs::read_dir(sysfs.join("cpufreq")) else {
+        return Vec::new();
+    };
+
+    let mut clusters: BTreeMap<u64, CpuCluster> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if !name.to_string_lossy().starts_with("policy") {
+            continue;
+        }
+
+        let policy = entry.path();
+        let threads = read_sys(&policy.join("affected_cpus"))
+            .map(|cpus| cpus.split_whitespace().count())
+            .unwrap_or(0);
+        let max_freq_mhz = read_khz(&policy.join("cpuinfo_max_freq"));
+        let cur_freq_mhz = read_khz(&policy.join("scaling_cur_freq"));
+
+        let cluster = clusters.entry(max_freq_mhz.unwrap_or(0)).or_insert(CpuCluster {
+            threads: 0,
+            cur_freq_mhz: None,
+            max_freq_mhz,
+        });
+        cluster.threads += threads;
+        cluster.cur_freq_mhz = cluster.cur_freq_mhz.max(cur_freq_mhz);
+    }
+
+    clusters.into_values().filter(|cluster| cluster.threads > 0).collect()
+}
+
+fn read_khz(path: &Path) -> Option<u64> {
+    read_sys(path)?.parse::<u64>().ok().map(|khz| khz / 1000)
+}
+
+fn read_sys(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn format_frequency(cur: Option<u64>, max: Option<u64>) -> Option<String> {
+    let ghz = |mhz: u64| format!("{:.2}", mhz as f64 / 1000.0);
+    match (cur, max) {
+        (Some(cur), Some(max)) if cur != max => Some(format!("{}/{} GHz", ghz(cur), ghz(max))),
+        (_, Some(mhz)) | (Some(mhz), None) => Some(format!("{} GHz", ghz(mhz))),
+        (None, None) => None,
+    }
+}
+
+fn describe_frequency(data: &ModuleData) -> Option<String> {
+    let integer = |data: &ModuleData, name| data.get(name).and_then(Value::as_u64);
+    let clusters = data.get("clusters").and_then(Value::as_list).unwrap_or_default();
+    let frequencies: Vec<String> = clusters
+        .iter()
+        .filter_map(|cluster| {
+            let frequency = format_frequency(
+                integer(cluster, "cur_freq_mhz"),
+                integer(cluster, "max_freq_mhz"),
+            )?;
+            if clusters.len() > 1 {
+                Some(format!("{}x {}", integer(cluster, "threads").unwrap_or(0), frequency))
+            } else {
+                Some(frequency)
+            }
+        })
+        .collect();
+
+    (!frequencies.is_empty()).then(|| frequencies.join(", "))
+}
+
+pub struct CpuModule {
+    usage_interval: Option<Duration>,
+}
+
+impl CpuModule {
+    pub fn new(cfg: &Config) -> Self {
+        Self {
+            usage_interval: cfg.cpu_usage_interval(),
+        }
+    }
+}
+
+impl InfoModule for CpuModule {
+    fn id(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn label(&self) -> &'static str {
+        "CPU"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f4bc}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let cpu = get_cpu_info()
+            .ok_or_else(|| ModuleError::Unavailable("/proc/cpuinfo is unavailable".into()))?;
+
+        let clusters = cpu
+            .clusters
+            .iter()
+            .map(|cluster| {
+                let mut data = ModuleData::default()
+                    .with("threads", Value::Integer(cluster.threads as u64));
+                if let Some(mhz) = cluster.cur_freq_mhz {
+                    data = data.with("cur_freq_mhz", Value::Integer(mhz));
+                }
+                if let Some(mhz) = cluster.max_freq_mhz {
+                    data = data.with("max_freq_mhz", Value::Integer(mhz));
+                }
+                data
+            })
+            .collect();
+
+        let mut data = ModuleData::default()
+            .with("model", cpu.model)
+            .with("cores", Value::Integer(cpu.cores as u64))
+            .with("threads", Value::Integer(cpu.threads as u64))
+            .with("clusters", Value::List(clusters));
+
+        if let Some(usage) = self.usage_interval.and_then(sample_usage) {
+            data = data.with("usage", Value::Percent(usage));
+        }
+
+        Ok(data)
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        let integer = |name| data.get(name).and_then(Value::as_u64);
+        let cores = integer("cores").unwrap_or(0);
+        let threads = integer("threads").unwrap_or(0);
+
+        let mut text = if cores != threads {
+            format!("{} ({}C/{}T)", data.text("model"), cores, threads)
+        } else {
+            format!("{} ({})", data.text("model"), threads)
+        };
+
+        if let Some(frequency) = describe_frequency(data) {
+            text.push_str(&format!(" @ {}", frequency));
+        }
+
+        if let Some(usage) = data.get("usage") {
+            text.push_str(&format!(" [{}]", usage));
+        }
+
+        paint_info(&text, cfg)
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["model", "cores", "threads", "frequency", "usage"]
+    }
+
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        let mut values = data.clone();
+        values.set("frequency", describe_frequency(data).unwrap_or_default());
+        vec![values]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn fake_sysfs(name: &str, topology: &[(&str, &str, Option<&str>)]) -> std::path::PathBuf {
+        let root = env::temp_dir().join(format!("hostfetch-cpu-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (cpu, (package, core, cluster)) in topology.iter().enumerate() {
+            let dir = root.join(format!("cpu{}", cpu)).join("topology");
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("physical_package_id"), package).unwrap();
+            fs::write(dir.join("core_id"), core).unwrap();
+            if let Some(cluster) = cluster {
+                fs::write(dir.join("cluster_id"), cluster).unwrap();
+            }
+        }
+        root
+    }
+
+    #[test]
+    fn counts_cores_per_cluster() {
+        let topology: Vec<_> = (0..8)
+            .map(|cpu| ("0", ["0", "1", "2", "3"][cpu % 4], Some(["0", "1"][cpu / 4])))
+            .collect();
+        let sysfs = fake_sysfs("clusters", &topology);
+        assert_eq!(count_cores(&sysfs, &(0..8).collect::<Vec<_>>()), Some(8));
+        fs::remove_dir_all(sysfs).unwrap();
+    }
+
+    #[test]
+    fn groups_online_cpus_by_cluster() {
+        let sysfs = env::temp_dir().join(format!("hostfetch-cpu-clusters-{}", process::id()));
+        let _ = fs::remove_dir_all(&sysfs);
+        let policies = [
+            ("policy0", "0 1 2 3", "1800000", "1200000"),
+            ("policy4", "4 5", "2400000", "2400000"),
+            ("policy6", "6", "2400000", "1800000"),
+            ("policy7", "", "3000000", "3000000"),
+        ];
+        for (name, affected, max, cur) in policies {
+            let policy = sysfs.join("cpufreq").join(name);
+            fs::create_dir_all(&policy).unwrap();
+            fs::write(policy.join("related_cpus"), "0 1 2 3 4 5 6 7").unwrap();
+            fs::write(policy.join("affected_cpus"), affected).unwrap();
+            fs::write(policy.join("cpuinfo_max_freq"), max).unwrap();
+            fs::write(policy.join("scaling_cur_freq"), cur).unwrap();
+        }
+
+        let clusters: Vec<_> = read_clusters(&sysfs)
+            .iter()
+            .map(|cluster| (cluster.threads, cluster.cur_freq_mhz, cluster.max_freq_mhz))
+            .collect();
+        assert_eq!(clusters, [(4, Some(1200), Some(1800)), (3, Some(2400), Some(2400))]);
+        fs::remove_dir_all(sysfs).unwrap();
+    }
+
+    #[test]
+    fn counts_smt_siblings_once() {
+        let topology = [("0", "0", None), ("0", "1", None), ("0", "0", None), ("0", "1", None)];
+        let sysfs = fake_sysfs("smt", &topology);
+        assert_eq!(count_cores(&sysfs, &[0, 1, 2, 3]), Some(2));
+        fs::remove_dir_all(sysfs).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+use std::env;
+
+use crate::config::Config;
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError};
+use crate::process;
+
+const KNOWN_DESKTOPS: [(&str, &str); 14] = [
+    ("gnome", "GNOME"),
+    ("kde", "KDE Plasma"),
+    ("plasma", "KDE Plasma"),
+    ("plasmawayland", "KDE Plasma"),
+    ("xfce", "Xfce"),
+    ("cinnamon", "Cinnamon"),
+    ("mate", "MATE"),
+    ("lxqt", "LXQt"),
+    ("lxde", "LXDE"),
+    ("budgie", "Budgie"),
+    ("unity", "Unity"),
+    ("pantheon", "Pantheon"),
+    ("deepin", "Deepin"),
+    ("cosmic", "COSMIC"),
+];
+
+// Process names as they appear in /proc/<pid>/comm, which the kernel cuts
+// to 15 bytes. Compositors come first so a nested X11 WM does not win.
+const KNOWN_WMS: [(&str, &str); 30] = [
+    ("sway", "Sway"),
+    ("Hyprland", "Hyprland"),
+    ("kwin_wayland", "KWin"),
+    ("gnome-shell", "Mutter"),
+    ("river", "river"),
+    ("wayfire", "Wayfire"),
+    ("labwc", "labwc"),
+    ("niri", "niri"),
+    ("weston", "Weston"),
+    ("cosmic-comp", "cosmic-comp"),
+    ("kwin_x11", "KWin"),
+    ("kwin", "KWin"),
+    ("mutter", "Mutter"),
+    ("muffin", "Muffin"),
+    ("cinnamon", "Muffin"),
+    ("marco", "Marco"),
+    ("xfwm4", "Xfwm4"),
+    ("openbox", "Openbox"),
+    ("i3", "i3"),
+    ("bspwm", "bspwm"),
+    ("dwm", "dwm"),
+    ("awesome", "Awesome"),
+    ("herbstluftwm", "herbstluftwm"),
+    ("xmonad", "xmonad"),
+    ("spectrwm", "spectrwm"),
+    ("fluxbox", "Fluxbox"),
+    ("icewm", "IceWM"),
+    ("fvwm", "FVWM"),
+    ("enlightenment", "Enlightenment"),
+    ("metacity", "Metacity"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionType {
+    Wayland,
+    X11,
+    Tty,
+}
+
+impl SessionType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionType::Wayland => "Wayland",
+            SessionType::X11 => "X11",
+            SessionType::Tty => "TTY",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DesktopInfo {
+    pub de: Option<String>,
+    pub wm: Option<String>,
+    pub session: SessionType,
+}
+
+pub fn get_desktop_info() -> DesktopInfo {
+    let session = detect_session();
+    let wm = if session == SessionType::Tty {
+        None
+    } else {
+        detect_wm(&process::process_names())
+    };
+
+    // Tiling WMs put their own name in XDG_CURRENT_DESKTOP.
+    let de = detect_de().filter(|de| wm.as_ref().is_none_or(|wm| !de.eq_ignore_ascii_case(wm)));
+
+    DesktopInfo { de, wm, session }
+}
+
+pub fn detect_session() -> SessionType {
+    match env::var("XDG_SESSION_TYPE").as_deref() {
+        Ok("wayland") => return SessionType::Wayland,
+        Ok("x11") => return SessionType::X11,
+        Ok("tty") => return SessionType::Tty,
+        _ => {}
+    }
+
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        SessionType::Wayland
+    } else if env::var_os("DISPLAY").is_some() {
+        SessionType::X11
+    } else {
+        SessionType::Tty
+    }
+}
+
+pub fn detect_de() -> Option<String> {
+    if let Ok(current) = env::var("XDG_CURRENT_DESKTOP")
+        && let Some(de) = normalize_de(current.split(':'))
+    {
+        return Some(de);
+    }
+
+    // Display managers sometimes set this to the full .desktop path.
+    let session = env::var("DESKTOP_SESSION").ok()?;
+    let session = session.rsplit('/').next().unwrap_or(&session);
+    normalize_de(std::iter::once(session.trim_end_matches(".desktop")))
+}
+
+fn normalize_de<'a>(names: impl Iterator<Item = &'a str>) -> Option<String> {
+    let names: Vec<&str> = names
+        .map(|name| name.trim().trim_start_matches("X-"))
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    for name in &names {
+        let lower = name.to_lowercase();
+        if let Some((_, de)) = KNOWN_DESKTOPS.iter().find(|(key, _)| *key == lower) {
+            return Some(de.to_string());
+        }
+    }
+
+    names.first().map(|name| name.to_string())
+}
+
+pub fn detect_wm(processes: &[String]) -> Option<String> {
+    KNOWN_WMS
+        .iter()
+        .find(|(comm, _)| {
+            processes.iter().any(|process| {
+                // xmonad runs as a compiled binary named after the platform,
+                // e.g. xmonad-x86_64-linux.
+                process == comm || (*comm == "xmonad" && process.starts_with("xmonad-"))
+            })
+        })
+        .map(|(_, name)| name.to_string())
+}
+
+pub struct DesktopModule;
+
+impl InfoModule for DesktopModule {
+    fn id(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn label(&self) -> &'static str {
+        "DE/WM"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f2d2}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let info = get_desktop_info();
+
+        let mut data = ModuleData::default();
+        if let Some(de) = info.de {
+            data = data.with("de", de);
+        }
+        if let Some(wm) = info.wm {
+            data = data.with("wm", wm);
+        }
+        Ok(data.with("session", info.session.name()))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        let session = data.text("session");
+        let text = match (data.get("de"), data.get("wm")) {
+            (Some(de), Some(wm)) => format!("{} ({}, {})", de, wm, session),
+            (Some(name), None) | (None, Some(name)) => format!("{} ({})", name, session),
+            (None, None) => session,
+        };
+
+        paint_info(&text, cfg)
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["de", "wm", "session"]
+    }
+}
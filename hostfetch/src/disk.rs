@@ -0,0 +1,198 @@
+use std::fs;
+
+use nix::sys::statvfs::statvfs;
+
+use crate::config::Config;
+use crate::module::{
+    format_bytes, list_items, paint_info, paint_percent, InfoModule, ModuleData, ModuleError, Value,
+};
+
+#[derive(Debug, Clone)]
+pub struct MountEntry {
+    pub device: String,
+    pub mount_point: String,
+    pub fs_type: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    pub mount: MountEntry,
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl DiskUsage {
+    // Same as df: reserved blocks count as neither used nor available.
+    pub fn percent(&self) -> f64 {
+        let usable = self.used_bytes + self.available_bytes;
+        if usable == 0 {
+            0.0
+        } else {
+            self.used_bytes as f64 / usable as f64 * 100.0
+        }
+    }
+}
+
+pub fn read_mounts() -> Vec<MountEntry> {
+    let Ok(content) = fs::read_to_string("/proc/self/mounts") else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(MountEntry {
+                device: unescape(fields.next()?),
+                mount_point: unescape(fields.next()?),
+                fs_type: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+// /proc/self/mounts encodes spaces and tabs in paths as octal escapes.
+fn unescape(field: &str) -> String {
+    let mut result = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let code: String = chars.by_ref().take(3).collect();
+            match u8::from_str_radix(&code, 8) {
+                Ok(byte) => result.push(byte as char),
+                Err(_) => {
+                    result.push(c);
+                    result.push_str(&code);
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+pub fn get_disk_usage(mounts: &[String], hide_fs: &[String]) -> Vec<DiskUsage> {
+    let entries = read_mounts();
+    let show_all = mounts.iter().any(|mount| mount == "*");
+
+    let mut selected: Vec<&MountEntry> = Vec::new();
+    for mount in mounts.iter().filter(|mount| *mount != "*") {
+        if let Some(entry) = entries.iter().rev().find(|entry| &entry.mount_point == mount) {
+            selected.push(entry);
+        }
+    }
+
+    if show_all {
+        for entry in &entries {
+            if !hide_fs.contains(&entry.fs_type)
+                && !selected.iter().any(|s| s.mount_point == entry.mount_point)
+            {
+                selected.push(entry);
+            }
+        }
+    }
+
+    selected
+        .into_iter()
+        .filter_map(|entry| {
+            let stat = statvfs(entry.mount_point.as_str()).ok()?;
+            let block = stat.fragment_size() as u64;
+            let total = stat.blocks() as u64 * block;
+            if total == 0 {
+                return None;
+            }
+
+            Some(DiskUsage {
+                mount: entry.clone(),
+                used_bytes: total - stat.blocks_free() as u64 * block,
+                total_bytes: total,
+                available_bytes: stat.blocks_available() as u64 * block,
+            })
+        })
+        .collect()
+}
+
+pub struct DiskModule {
+    mounts: Vec<String>,
+    hide_fs: Vec<String>,
+}
+
+impl DiskModule {
+    pub fn new(cfg: &Config) -> Self {
+        Self {
+            mounts: cfg.disk.mounts.clone(),
+            hide_fs: cfg.disk.hide_fs.clone(),
+        }
+    }
+}
+
+impl InfoModule for DiskModule {
+    fn id(&self) -> &'static str {
+        "disk"
+    }
+
+    fn label(&self) -> &'static str {
+        "Disk"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f0a0}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let disks: Vec<ModuleData> = get_disk_usage(&self.mounts, &self.hide_fs)
+            .into_iter()
+            .map(|disk| {
+                let percent = disk.percent();
+                ModuleData::default()
+                    .with("mount", disk.mount.mount_point)
+                    .with("device", disk.mount.device)
+                    .with("fs", disk.mount.fs_type)
+                    .with("used", Value::Bytes(disk.used_bytes))
+                    .with("total", Value::Bytes(disk.total_bytes))
+                    .with("available", Value::Bytes(disk.available_bytes))
+                    .with("percent", Value::Percent(percent))
+            })
+            .collect();
+
+        if disks.is_empty() {
+            return Err(ModuleError::Unavailable("no matching mounts".into()));
+        }
+
+        Ok(ModuleData::default().with("disks", Value::List(disks)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        self.render_lines(data, cfg).join(", ")
+    }
+
+    fn render_lines(&self, data: &ModuleData, cfg: &Config) -> Vec<String> {
+        data.get("disks")
+            .and_then(Value::as_list)
+            .unwrap_or_default()
+            .iter()
+            .map(|disk| {
+                let bytes = |name| disk.get(name).and_then(Value::as_u64).unwrap_or(0);
+                let usage = format!(
+                    "{}: {} / {}",
+                    disk.text("mount"),
+                    format_bytes(bytes("used")),
+                    format_bytes(bytes("total"))
+                );
+                let percent = disk.get("percent").and_then(Value::as_f64).unwrap_or(0.0);
+
+                format!("{} ({})", paint_info(&usage, cfg), paint_percent(percent))
+            })
+            .collect()
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["mount", "device", "fs", "used", "total", "available", "percent"]
+    }
+
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        list_items(data, "disks")
+    }
+}
@@ -0,0 +1,257 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+use crate::check;
+use crate::config::Config;
+use crate::module::Registry;
+
+// The effective settings as dotted keys, in the order `config show` uses.
+pub fn list(cfg: &Config) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+    let document: DocumentMut = toml::to_string(cfg)?.parse()?;
+    let mut entries = Vec::new();
+    flatten(document.as_table(), "", &mut entries);
+    Ok(entries)
+}
+
+fn flatten(table: &dyn TableLike, prefix: &str, entries: &mut Vec<(String, String)>) {
+    for (key, item) in table.iter() {
+        let name = match prefix {
+            "" => key.to_string(),
+            _ => format!("{}.{}", prefix, key),
+        };
+        match item {
+            Item::Value(value) => entries.push((name, value.to_string().trim().to_string())),
+            item => {
+                if let Some(inner) = item.as_table_like() {
+                    flatten(inner, &name, entries);
+                }
+            }
+        }
+    }
+}
+
+// Strings come back without quotes so the output can be used in scripts;
+// a table is printed as an inline table.
+pub fn get(cfg: &Config, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let document: DocumentMut = toml::to_string(cfg)?.parse()?;
+    let item = key
+        .split('.')
+        .try_fold(document.as_item(), |item, name| item.get(name));
+
+    Ok(match item {
+        Some(Item::Value(Value::String(text))) => Some(text.value().clone()),
+        Some(Item::Value(value)) => Some(value.to_string().trim().to_string()),
+        Some(Item::Table(table)) => Some(table.clone().into_inline_table().to_string().trim().to_string()),
+        _ => None,
+    })
+}
+
+// The value is read as TOML (numbers, booleans, arrays, quoted strings) and
+// anything else is taken as a plain string, so `set info.info_color red`
+// works without shell-escaped quotes.
+pub fn set(path: &Path, key: &str, value: &str, registry: &Registry) -> Result<(), Box<dyn Error>> {
+    let content = read(path)?;
+    let mut document: DocumentMut = content.parse()?;
+    let (sections, name) = split_key(key)?;
+    let table = table_mut(document.as_table_mut(), &sections)?;
+
+    let mut value = value.parse::<Value>().unwrap_or_else(|_| Value::from(value));
+
+    // A file that already fails to load would hide a type error in the new
+    // value, so the key is also loaded on its own.
+    let mut single = DocumentMut::new();
+    table_mut(single.as_table_mut(), &sections)?.insert(name, Item::Value(value.clone()));
+    if let Err(e) = toml::from_str::<Config>(&single.to_string()) {
+        return Err(format!("not saved: {}", e.message().trim()).into());
+    }
+
+    match table.get_mut(name) {
+        Some(Item::Value(old)) => {
+            *value.decor_mut() = old.decor().clone();
+            *old = value;
+        }
+        Some(item) => *item = Item::Value(value),
+        None => {
+            table.insert(name, Item::Value(value));
+        }
+    }
+
+    save(path, &content, &document.to_string(), registry)
+}
+
+pub fn unset(path: &Path, key: &str, registry: &Registry) -> Result<(), Box<dyn Error>> {
+    let content = read(path)?;
+    let mut document: DocumentMut = content.parse()?;
+    let (sections, name) = split_key(key)?;
+
+    let table = sections.iter().try_fold(document.as_table_mut() as &mut dyn TableLike, |table, section| {
+        table.get_mut(section)?.as_table_like_mut()
+    });
+    if table.and_then(|table| table.remove(name)).is_none() {
+        return Err(format!("{} is not set in {}", key, path.display()).into());
+    }
+
+    save(path, &content, &document.to_string(), registry)
+}
+
+fn read(path: &Path) -> Result<String, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+    }
+}
+
+fn split_key(key: &str) -> Result<(Vec<&str>, &str), String> {
+    let mut sections: Vec<&str> = key.split('.').collect();
+    let name = sections.pop().unwrap_or_default();
+    if name.is_empty() || sections.iter().any(|section| section.is_empty()) {
+        return Err(format!("invalid key '{}'", key));
+    }
+    Ok((sections, name))
+}
+
+// Missing tables are created without a header of their own when they only
+// hold other tables, as with [modules.formats].
+fn table_mut<'a>(root: &'a mut Table, sections: &[&str]) -> Result<&'a mut dyn TableLike, String> {
+    let mut table: &mut dyn TableLike = root;
+    for section in sections {
+        if !table.contains_key(section) {
+            let mut new = Table::new();
+            new.set_implicit(true);
+            table.insert(section, Item::Table(new));
+        }
+        table = table
+            .get_mut(section)
+            .and_then(Item::as_table_like_mut)
+            .ok_or_else(|| format!("'{}' is not a table", section))?;
+    }
+    Ok(table)
+}
+
+// Edits are refused when they introduce a problem `config check` would
+// report; mistakes already in the file do not block unrelated changes.
+fn save(path: &Path, before: &str, after: &str, registry: &Registry) -> Result<(), Box<dyn Error>> {
+    let known: Vec<String> = check::check(path, before, registry)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect();
+    let introduced: Vec<String> = check::check(path, after, registry)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .filter(|message| !known.contains(message))
+        .collect();
+    if !introduced.is_empty() {
+        return Err(format!("not saved: {}", introduced.join("; ")).into());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, after)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+    use std::process;
+
+    const CONFIG: &str = "\
+# house style
+logo = \"arch\"
+
+[info]
+# label colour
+name_color = \"white\"
+info_color = \"blue\" # values
+border_color = \"blue\"
+
+[modules]
+# fast machines only
+timeout_ms = 500
+";
+
+    fn temp_config(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("hostfetch-edit-{}-{}.toml", name, process::id()));
+        fs::write(&path, CONFIG).unwrap();
+        path
+    }
+
+    #[test]
+    fn gets_values_and_tables() {
+        let config: Config = toml::from_str(
+            "[host]\ncolor = \"red\"\n[modules.timeouts]\nhost = 3000\n",
+        )
+        .unwrap();
+
+        assert_eq!(get(&config, "host.color").unwrap().as_deref(), Some("red"));
+        assert_eq!(get(&config, "modules.timeout_ms").unwrap().as_deref(), Some("1000"));
+        assert_eq!(get(&config, "modules.timeouts").unwrap().as_deref(), Some("{ host = 3000 }"));
+        assert_eq!(get(&config, "modules.formats").unwrap().as_deref(), Some("{}"));
+        assert_eq!(get(&config, "host.colour").unwrap(), None);
+    }
+
+    #[test]
+    fn set_and_unset_keep_comments_and_order() {
+        let path = temp_config("roundtrip");
+        let registry = Registry::builtin(&Config::default());
+
+        set(&path, "info.info_color", "#ff8800", &registry).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            CONFIG.replace("info_color = \"blue\"", "info_color = \"#ff8800\"")
+        );
+
+        unset(&path, "info.border_color", &registry).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            CONFIG
+                .replace("info_color = \"blue\"", "info_color = \"#ff8800\"")
+                .replace("border_color = \"blue\"\n", "")
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn set_adds_missing_tables() {
+        let path = temp_config("tables");
+        let registry = Registry::builtin(&Config::default());
+
+        set(&path, "modules.formats.ram", "{used} / {total}", &registry).unwrap();
+        set(&path, "cpu.usage", "true", &registry).unwrap();
+        let config: Config = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config.module_format("ram"), Some("{used} / {total}"));
+        assert!(config.cpu.usage);
+        assert!(fs::read_to_string(&path).unwrap().starts_with(CONFIG));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn invalid_values_are_not_saved() {
+        let path = temp_config("invalid");
+        let registry = Registry::builtin(&Config::default());
+
+        assert!(set(&path, "info.info_color", "magneta", &registry).is_err());
+        assert!(set(&path, "modules.timeout_ms", "fast", &registry).is_err());
+        assert!(set(&path, "modules.formats.cpu", "{nope}", &registry).is_err());
+        assert!(set(&path, "info.colr", "red", &registry).is_err());
+        assert!(unset(&path, "info.label_align", &registry).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG);
+
+        let broken = format!("logo_width = \"wide\"\n{}", CONFIG);
+        fs::write(&path, &broken).unwrap();
+        assert!(set(&path, "modules.order", "os,kernel", &registry).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), broken);
+
+        fs::remove_file(path).unwrap();
+    }
+}
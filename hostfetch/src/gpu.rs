@@ -0,0 +1,307 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::module::{list_items, paint_info, InfoModule, ModuleData, ModuleError, Value};
+
+const SYSFS_DRM: &str = "/sys/class/drm";
+
+const PCI_IDS_PATHS: [&str; 4] = [
+    "/usr/share/hwdata/pci.ids",
+    "/usr/share/misc/pci.ids",
+    "/usr/share/pci.ids",
+    "/data/data/com.termux/files/usr/share/hwdata/pci.ids",
+];
+
+#[derive(Debug, Clone)]
+pub struct GpuInfo {
+    pub vendor_id: Option<String>,
+    pub device_id: Option<String>,
+    pub vendor: Option<String>,
+    pub name: Option<String>,
+    pub driver: Option<String>,
+}
+
+pub fn get_gpus() -> Vec<GpuInfo> {
+    let pci_ids = PCI_IDS_PATHS
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok());
+
+    read_gpus(Path::new(SYSFS_DRM), pci_ids.as_deref())
+}
+
+pub fn read_gpus(drm: &Path, pci_ids: Option<&str>) -> Vec<GpuInfo> {
+    let Ok(entries) = fs::read_dir(drm) else {
+        return Vec::new();
+    };
+
+    let mut cards: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("card") && !name.contains('-'))
+        .collect();
+    cards.sort();
+
+    let mut seen = BTreeSet::new();
+    let mut gpus = Vec::new();
+    for card in cards {
+        let device = drm.join(&card).join("device");
+        let key = fs::canonicalize(&device).unwrap_or_else(|_| device.clone());
+        if !seen.insert(key) {
+            continue;
+        }
+
+        let vendor_id = read_pci_id(&device.join("vendor"));
+        let device_id = read_pci_id(&device.join("device"));
+        let driver = fs::read_link(device.join("driver"))
+            .ok()
+            .and_then(|link| link.file_name().map(|name| name.to_string_lossy().into_owned()));
+
+        if vendor_id.is_none() && driver.is_none() {
+            continue;
+        }
+
+        let (vendor, name) = match (pci_ids, &vendor_id, &device_id) {
+            (Some(pci_ids), Some(vendor_id), Some(device_id)) => {
+                lookup_pci(pci_ids, vendor_id, device_id)
+            }
+            _ => (None, None),
+        };
+
+        gpus.push(GpuInfo {
+            vendor_id,
+            device_id,
+            vendor,
+            name,
+            driver,
+        });
+    }
+
+    gpus
+}
+
+fn read_pci_id(path: &Path) -> Option<String> {
+    let id = fs::read_to_string(path).ok()?;
+    let id = id.trim().trim_start_matches("0x").to_lowercase();
+    (!id.is_empty()).then_some(id)
+}
+
+// pci.ids lists each vendor at column 0 followed by its devices indented by
+// one tab; subsystems (two tabs) and the class section at the end are skipped.
+fn lookup_pci(pci_ids: &str, vendor_id: &str, device_id: &str) -> (Option<String>, Option<String>) {
+    let mut vendor = None;
+
+    for line in pci_ids.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with('\t') {
+            if vendor.is_some() {
+                break;
+            }
+            if let Some((id, name)) = line.split_once("  ")
+                && id == vendor_id
+            {
+                vendor = Some(name.trim().to_string());
+            }
+            continue;
+        }
+
+        if vendor.is_some()
+            && !line.starts_with("\t\t")
+            && let Some((id, name)) = line.trim_start().split_once("  ")
+            && id == device_id
+        {
+            return (vendor, Some(name.trim().to_string()));
+        }
+    }
+
+    (vendor, None)
+}
+
+fn short_vendor(vendor: &str) -> String {
+    if vendor.contains("NVIDIA") {
+        return "NVIDIA".to_string();
+    }
+    if vendor.contains("AMD") || vendor.contains("ATI") {
+        return "AMD".to_string();
+    }
+    if vendor.starts_with("Intel") {
+        return "Intel".to_string();
+    }
+
+    vendor
+        .trim_end_matches(" Corporation")
+        .trim_end_matches(", Inc.")
+        .trim_end_matches(" Inc.")
+        .trim_end_matches(" Ltd.")
+        .to_string()
+}
+
+fn short_name(name: &str) -> &str {
+    match (name.find('['), name.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &name[start + 1..end],
+        _ => name,
+    }
+}
+
+fn describe(gpu: &ModuleData) -> String {
+    let text = |name| gpu.get(name).map(Value::to_string);
+
+    let mut description = match (text("vendor"), text("name")) {
+        (Some(vendor), Some(name)) => format!("{} {}", short_vendor(&vendor), short_name(&name)),
+        _ => match (text("vendor_id"), text("device_id")) {
+            (Some(vendor_id), Some(device_id)) => format!("{}:{}", vendor_id, device_id),
+            _ => String::new(),
+        },
+    };
+
+    if let Some(driver) = text("driver") {
+        if description.is_empty() {
+            description = driver;
+        } else {
+            description.push_str(&format!(" [{}]", driver));
+        }
+    }
+
+    description
+}
+
+pub struct GpuModule;
+
+impl InfoModule for GpuModule {
+    fn id(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn label(&self) -> &'static str {
+        "GPU"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f08ae}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let gpus = get_gpus();
+        if gpus.is_empty() {
+            return Err(ModuleError::Unavailable("no GPU found".into()));
+        }
+
+        let gpus = gpus
+            .into_iter()
+            .map(|gpu| {
+                let mut data = ModuleData::default();
+                for (name, value) in [
+                    ("vendor_id", gpu.vendor_id),
+                    ("device_id", gpu.device_id),
+                    ("vendor", gpu.vendor),
+                    ("name", gpu.name),
+                    ("driver", gpu.driver),
+                ] {
+                    if let Some(value) = value {
+                        data = data.with(name, value);
+                    }
+                }
+                data
+            })
+            .collect();
+
+        Ok(ModuleData::default().with("gpus", Value::List(gpus)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        self.render_lines(data, cfg).join(", ")
+    }
+
+    fn render_lines(&self, data: &ModuleData, cfg: &Config) -> Vec<String> {
+        data.get("gpus")
+            .and_then(Value::as_list)
+            .unwrap_or_default()
+            .iter()
+            .map(|gpu| paint_info(&describe(gpu), cfg))
+            .collect()
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["description", "vendor", "name", "driver", "vendor_id", "device_id"]
+    }
+
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        list_items(data, "gpus")
+            .into_iter()
+            .map(|gpu| {
+                let description = describe(&gpu);
+                gpu.with("description", description)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::os::unix::fs::symlink;
+    use std::process;
+
+    const PCI_IDS: &str = "\
+# comment
+1002  Advanced Micro Devices, Inc. [AMD/ATI]
+\t73bf  Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]
+\t\t1002 0e3a  Radeon RX 6900 XT
+10de  NVIDIA Corporation
+\t2684  AD102 [GeForce RTX 4090]
+C 03  Display controller
+\t00  VGA compatible controller
+";
+
+    #[test]
+    fn reads_cards_once_per_device() {
+        let root = env::temp_dir().join(format!("hostfetch-gpu-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let pci = root.join("devices/0000:03:00.0");
+        fs::create_dir_all(&pci).unwrap();
+        fs::create_dir_all(root.join("drivers/amdgpu")).unwrap();
+        fs::write(pci.join("vendor"), "0x1002\n").unwrap();
+        fs::write(pci.join("device"), "0x73BF\n").unwrap();
+        symlink(root.join("drivers/amdgpu"), pci.join("driver")).unwrap();
+
+        let drm = root.join("drm");
+        for card in ["card0", "card1", "card0-HDMI-A-1"] {
+            fs::create_dir_all(drm.join(card)).unwrap();
+            symlink(&pci, drm.join(card).join("device")).unwrap();
+        }
+
+        let gpus = read_gpus(&drm, Some(PCI_IDS));
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].vendor_id.as_deref(), Some("1002"));
+        assert_eq!(gpus[0].device_id.as_deref(), Some("73bf"));
+        assert_eq!(gpus[0].vendor.as_deref(), Some("Advanced Micro Devices, Inc. [AMD/ATI]"));
+        assert_eq!(
+            gpus[0].name.as_deref(),
+            Some("Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]")
+        );
+        assert_eq!(gpus[0].driver.as_deref(), Some("amdgpu"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn looks_up_devices_under_their_vendor_only() {
+        assert_eq!(
+            lookup_pci(PCI_IDS, "10de", "2684"),
+            (
+                Some("NVIDIA Corporation".to_string()),
+                Some("AD102 [GeForce RTX 4090]".to_string())
+            )
+        );
+        assert_eq!(
+            lookup_pci(PCI_IDS, "10de", "73bf"),
+            (Some("NVIDIA Corporation".to_string()), None)
+        );
+        assert_eq!(lookup_pci(PCI_IDS, "8086", "73bf"), (None, None));
+    }
+}
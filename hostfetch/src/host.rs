@@ -2,6 +2,8 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use crate::module::{InfoModule, ModuleData, ModuleError};
+
 pub fn get_device_info() -> String {
     let mut result = String::new();
 
@@ -89,7 +91,7 @@ fn read_dmi_field(field: &str) -> Option<String> {
     ];
 
     paths.iter()
-        .find_map(|path| read_sys_file(path))
+        .find_map(read_sys_file)
 }
 
 fn read_sys_file<P: AsRef<Path>>(path: P) -> Option<String> {
@@ -108,3 +110,27 @@ fn get_property(prop: &str) -> Option<String> {
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
 }
+
+pub struct HostModule;
+
+impl InfoModule for HostModule {
+    fn id(&self) -> &'static str {
+        "host"
+    }
+
+    fn label(&self) -> &'static str {
+        "Host"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f109}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        Ok(ModuleData::default().with("model", get_device_info()))
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["model"]
+    }
+}
@@ -1,6 +1,5 @@
 use std::ffi::CStr;
 use std::io;
-use libc;
 
 const HOSTNAME_MAX_LEN: usize = 256;
 
@@ -32,10 +31,7 @@ fn try_hostname_command(hostname: &mut String) -> io::Result<()> {
         .output()?;
 
     if !output.status.success() {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "Hostname command failed"
-        ))
+        return Err(io::Error::other("Hostname command failed"))
     }
 
     *hostname = String::from_utf8(output.stdout)
@@ -0,0 +1,354 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+const KITTY_CHUNK: usize = 4096;
+
+// Cell size in pixels assumed when the terminal does not report one.
+const DEFAULT_CELL: (usize, usize) = (10, 20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+// Terminals known to implement one of the protocols, matched against the
+// name from terminal::detect_terminal (which may be a raw $TERM or
+// $TERM_PROGRAM value). Most terminals and ssh sessions set $TERM to
+// "xterm-256color", so XTerm itself only counts when detected by name.
+pub fn detect_protocol(terminal: &str) -> Option<Protocol> {
+    let lowercase = terminal.to_lowercase();
+    let matches = |names: &[&str]| names.iter().any(|name| lowercase.contains(name));
+
+    if matches(&["kitty", "ghostty"]) {
+        Some(Protocol::Kitty)
+    } else if matches(&["iterm", "wezterm"]) {
+        Some(Protocol::Iterm2)
+    } else if matches(&["foot", "mlterm", "konsole", "contour"]) || terminal == "XTerm" {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rgba {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl Rgba {
+    fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn resize(&self, width: usize, height: usize) -> Rgba {
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(self.pixel(x * self.width / width, y * self.height / height));
+            }
+        }
+        Rgba { width, height, pixels }
+    }
+}
+
+pub fn decode_png(png: &[u8]) -> Result<Rgba, png::DecodingError> {
+    let mut decoder = png::Decoder::new(png);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer)?;
+    let data = &buffer[..frame.buffer_size()];
+
+    let pixels = match frame.color_type {
+        png::ColorType::Rgba => data.chunks_exact(4).map(|p| [p[0], p[1], p[2], p[3]]).collect(),
+        png::ColorType::Rgb => data.chunks_exact(3).map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => data.chunks_exact(2).map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        // Indexed images are expanded by normalize_to_color8.
+        png::ColorType::Grayscale | png::ColorType::Indexed => {
+            data.iter().map(|&v| [v, v, v, 255]).collect()
+        }
+    };
+
+    Ok(Rgba {
+        width: frame.width as usize,
+        height: frame.height as usize,
+        pixels,
+    })
+}
+
+// Kitty accepts the PNG as is; it is split into base64 chunks of at most
+// 4096 bytes, each flagged with whether more follow. C=1 leaves the cursor
+// where it was and q=2 silences the terminal's replies.
+pub fn encode_kitty(png: &[u8], columns: usize, rows: usize) -> String {
+    let data = STANDARD.encode(png);
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+
+    let mut output = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            output.push_str(&format!(
+                "\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\",
+                columns, rows, more, chunk
+            ));
+        } else {
+            output.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    output
+}
+
+pub fn encode_iterm2(png: &[u8], columns: usize, rows: usize) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        png.len(),
+        columns,
+        rows,
+        STANDARD.encode(png)
+    )
+}
+
+// Colours are reduced to a 6x6x6 cube and only the ones in use are
+// defined. Pixels with alpha below half are left unset, which the P2=1
+// parameter renders as transparent.
+pub fn encode_sixel(image: &Rgba) -> String {
+    let level = |value: u8| (value as usize * 5 + 127) / 255;
+    let index = |pixel: [u8; 4]| {
+        (pixel[3] >= 128).then(|| level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2]))
+    };
+    let indices: Vec<Option<usize>> = image.pixels.iter().map(|&pixel| index(pixel)).collect();
+
+    let mut output = format!("\x1bP0;1;0q\"1;1;{};{}", image.width, image.height);
+
+    let used: BTreeSet<usize> = indices.iter().flatten().copied().collect();
+    for &color in &used {
+        let percent = |level: usize| level * 100 / 5;
+        output.push_str(&format!(
+            "#{};2;{};{};{}",
+            color,
+            percent(color / 36),
+            percent(color / 6 % 6),
+            percent(color % 6)
+        ));
+    }
+
+    for top in (0..image.height).step_by(6) {
+        let band = top..(top + 6).min(image.height);
+        let colors: BTreeSet<usize> = band
+            .clone()
+            .flat_map(|y| &indices[y * image.width..(y + 1) * image.width])
+            .flatten()
+            .copied()
+            .collect();
+
+        for (n, &color) in colors.iter().enumerate() {
+            if n > 0 {
+                output.push('$');
+            }
+            output.push_str(&format!("#{}", color));
+
+            let sixels = (0..image.width).map(|x| {
+                let bits = band
+                    .clone()
+                    .enumerate()
+                    .filter(|&(_, y)| indices[y * image.width + x] == Some(color))
+                    .fold(0u8, |bits, (row, _)| bits | 1 << row);
+                (63 + bits) as char
+            });
+            push_runs(&mut output, sixels);
+        }
+        output.push('-');
+    }
+
+    output.push_str("\x1b\\");
+    output
+}
+
+fn push_runs(output: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |output: &mut String, (sixel, count): (char, usize)| {
+        if count > 3 {
+            output.push_str(&format!("!{}{}", count, sixel));
+        } else {
+            output.extend(std::iter::repeat_n(sixel, count));
+        }
+    };
+
+    for sixel in sixels {
+        run = match run {
+            Some((current, count)) if current == sixel => Some((current, count + 1)),
+            Some(previous) => {
+                flush(output, previous);
+                Some((sixel, 1))
+            }
+            None => Some((sixel, 1)),
+        };
+    }
+    if let Some(run) = run {
+        flush(output, run);
+    }
+}
+
+pub fn cell_size() -> (usize, usize) {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+    if !ok || size.ws_col == 0 || size.ws_row == 0 {
+        return DEFAULT_CELL;
+    }
+    // Some terminals report fewer pixels than cells, or none at all.
+    match (
+        size.ws_xpixel as usize / size.ws_col as usize,
+        size.ws_ypixel as usize / size.ws_row as usize,
+    ) {
+        (0, _) | (_, 0) => DEFAULT_CELL,
+        cell => cell,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageLogo {
+    pub escape: String,
+    pub columns: usize,
+    pub rows: usize,
+}
+
+pub fn encode(
+    png: &[u8],
+    protocol: Protocol,
+    columns: usize,
+    cell: (usize, usize),
+) -> Result<ImageLogo, png::DecodingError> {
+    let image = decode_png(png)?;
+    let columns = columns.max(1);
+    let width = columns * cell.0;
+    let height = (width * image.height / image.width.max(1)).max(1);
+    let rows = height.div_ceil(cell.1);
+
+    let escape = match protocol {
+        Protocol::Kitty => encode_kitty(png, columns, rows),
+        Protocol::Iterm2 => encode_iterm2(png, columns, rows),
+        Protocol::Sixel => encode_sixel(&image.resize(width, height)),
+    };
+
+    Ok(ImageLogo { escape, columns, rows })
+}
+
+pub fn load(path: &Path, protocol: Protocol, columns: usize) -> Result<ImageLogo, Box<dyn Error>> {
+    let png = fs::read(path)?;
+    Ok(encode(&png, protocol, columns, cell_size())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    fn png(width: u32, height: u32, pixels: &[[u8; 4]]) -> Vec<u8> {
+        let mut output = Vec::new();
+        let mut encoder = png::Encoder::new(&mut output, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels.concat()).unwrap();
+        writer.finish().unwrap();
+        output
+    }
+
+    #[test]
+    fn detects_protocols() {
+        assert_eq!(detect_protocol("xterm-kitty"), Some(Protocol::Kitty));
+        assert_eq!(detect_protocol("WezTerm"), Some(Protocol::Iterm2));
+        assert_eq!(detect_protocol("foot"), Some(Protocol::Sixel));
+        assert_eq!(detect_protocol("Konsole"), Some(Protocol::Sixel));
+        assert_eq!(detect_protocol("mlterm"), Some(Protocol::Sixel));
+        assert_eq!(detect_protocol("XTerm"), Some(Protocol::Sixel));
+        assert_eq!(detect_protocol("xterm-256color"), None);
+        assert_eq!(detect_protocol("xterm"), None);
+    }
+
+    #[test]
+    fn kitty_sends_small_images_in_one_chunk() {
+        let png = png(2, 2, &[RED, GREEN, BLUE, CLEAR]);
+        let logo = encode(&png, Protocol::Kitty, 2, (10, 20)).unwrap();
+        assert_eq!((logo.columns, logo.rows), (2, 1));
+        assert_eq!(
+            logo.escape,
+            format!("\x1b_Ga=T,f=100,c=2,r=1,C=1,q=2,m=0;{}\x1b\\", STANDARD.encode(&png))
+        );
+    }
+
+    #[test]
+    fn kitty_splits_large_images_into_chunks() {
+        let data = vec![0u8; 4000];
+        let encoded = STANDARD.encode(&data);
+        assert_eq!(encoded.len(), 5336);
+
+        assert_eq!(
+            encode_kitty(&data, 24, 12),
+            format!(
+                "\x1b_Ga=T,f=100,c=24,r=12,C=1,q=2,m=1;{}\x1b\\\x1b_Gm=0;{}\x1b\\",
+                &encoded[..4096],
+                &encoded[4096..]
+            )
+        );
+    }
+
+    #[test]
+    fn iterm2_sends_the_file_size() {
+        let png = png(2, 2, &[RED, GREEN, BLUE, CLEAR]);
+        assert_eq!(
+            encode_iterm2(&png, 2, 1),
+            format!(
+                "\x1b]1337;File=inline=1;size={};width=2;height=1;preserveAspectRatio=1:{}\x07",
+                png.len(),
+                STANDARD.encode(&png)
+            )
+        );
+    }
+
+    #[test]
+    fn sixel_defines_used_colours_and_leaves_clear_pixels_unset() {
+        let png = png(2, 2, &[RED, GREEN, BLUE, CLEAR]);
+        let logo = encode(&png, Protocol::Sixel, 1, (2, 2)).unwrap();
+        assert_eq!(
+            logo.escape,
+            "\x1bP0;1;0q\"1;1;2;2\
+             #5;2;0;0;100#30;2;0;100;0#180;2;100;0;0\
+             #5A?$#30?@$#180@?-\x1b\\"
+        );
+    }
+
+    #[test]
+    fn sixel_run_length_encodes_repeats() {
+        let image = Rgba {
+            width: 5,
+            height: 1,
+            pixels: vec![RED; 5],
+        };
+        assert_eq!(encode_sixel(&image), "\x1bP0;1;0q\"1;1;5;1#180;2;100;0;0#180!5@-\x1b\\");
+
+        let image = Rgba {
+            width: 3,
+            height: 1,
+            pixels: vec![RED; 3],
+        };
+        assert_eq!(encode_sixel(&image), "\x1bP0;1;0q\"1;1;3;1#180;2;100;0;0#180@@@-\x1b\\");
+    }
+}
@@ -1,35 +1,57 @@
-use std::process::Command;
-use std::string::FromUtf8Error;
 
-#[derive(Debug)]
- pub struct UnameResults {
+use std::io;
+
+use nix::sys::utsname::uname;
+
+use crate::config::Config;
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError};
+
+#[derive(Debug, Clone)]
+pub struct UnameResults {
     pub uname: String,
     pub kernel_version: String,
+    pub machine: String,
 }
 
-pub fn get_uname_data() -> Result<UnameResults, String> {
-    let uname = Command::new("uname")
-        .arg("-s")
-        .output()
-        .map_err(|e| format!("uname error: {}", e))?;
+pub fn get_uname_data() -> io::Result<UnameResults> {
+    let uts = uname()?;
+
+    Ok(UnameResults {
+        uname: uts.sysname().to_string_lossy().into_owned(),
+        kernel_version: uts.release().to_string_lossy().into_owned(),
+        machine: uts.machine().to_string_lossy().into_owned(),
+    })
+}
 
-    let kernel_version = Command::new("uname")
-        .arg("-r")
-        .output()
-        .map_err(|e| format!("uname -r error: {}", e))?;
+pub struct KernelModule;
 
-    if !uname.status.success() || !kernel_version.status.success() {
-        return Err("Command execution failed".into());
+impl InfoModule for KernelModule {
+    fn id(&self) -> &'static str {
+        "kernel"
     }
 
-    let parse_output = |output: Vec<u8>| -> Result<String, FromUtf8Error> {
-        String::from_utf8(output).map(|s| s.trim().to_string())
-    };
+    fn label(&self) -> &'static str {
+        "Kernel"
+    }
 
-    Ok(UnameResults {
-        uname: parse_output(uname.stdout)
-            .map_err(|e| format!("kernel name parsing error: {}", e))?,
-        kernel_version: parse_output(kernel_version.stdout)
-            .map_err(|e| format!("kernel version parsing error: {}", e))?,
-    })
-}
\ No newline at end of file
+    fn icon(&self) -> &'static str {
+        "\u{f013}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let data = get_uname_data()?;
+
+        Ok(ModuleData::default()
+            .with("name", data.uname)
+            .with("release", data.kernel_version)
+            .with("arch", data.machine))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        paint_info(&format!("{} {}", data.text("name"), data.text("release")), cfg)
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["name", "release", "arch"]
+    }
+}
@@ -0,0 +1,110 @@
+pub mod config;
+
+// Used by the hostfetch binary; not part of the library API.
+#[doc(hidden)]
+pub mod check;
+#[doc(hidden)]
+pub mod edit;
+#[doc(hidden)]
+pub mod hostname;
+#[doc(hidden)]
+pub mod username;
+#[doc(hidden)]
+pub mod module;
+#[doc(hidden)]
+pub mod logo;
+#[doc(hidden)]
+pub mod image;
+#[doc(hidden)]
+pub mod width;
+
+mod oschecker;
+mod host;
+mod kernel;
+mod uptime;
+mod load_average;
+mod ram;
+mod swap;
+mod terminal;
+mod shell;
+mod locale;
+mod cpu;
+mod gpu;
+mod disk;
+mod network;
+mod battery;
+mod packages;
+mod process;
+mod desktop;
+mod template;
+
+pub use battery::Battery;
+pub use cpu::{CpuCluster, CpuInfo};
+pub use desktop::{DesktopInfo, SessionType};
+pub use disk::DiskUsage;
+pub use gpu::GpuInfo;
+pub use kernel::UnameResults;
+pub use load_average::LoadAverage;
+pub use network::{InterfaceAddress, NetworkInterface};
+pub use packages::PackageCount;
+pub use ram::MemoryData;
+pub use swap::SwapData;
+
+#[derive(Debug, Clone)]
+pub struct SystemInfo {
+    pub os: Option<String>,
+    pub device: String,
+    pub kernel: Option<UnameResults>,
+    pub uptime: Option<u64>,
+    pub loadavg: Option<LoadAverage>,
+    pub cpu: Option<CpuInfo>,
+    pub gpus: Vec<GpuInfo>,
+    pub memory: MemoryData,
+    pub swap: Option<SwapData>,
+    pub disks: Vec<DiskUsage>,
+    pub network: Vec<NetworkInterface>,
+    pub batteries: Vec<Battery>,
+    pub packages: Vec<PackageCount>,
+    pub locale: Option<String>,
+    pub desktop: DesktopInfo,
+    pub shell: String,
+    pub terminal: String,
+    pub user: Option<String>,
+    pub hostname: Option<String>,
+}
+
+pub fn collect() -> SystemInfo {
+    collect_with(&config::Config::default())
+}
+
+// Uses the same disk, network and battery settings as the modules, so the
+// result matches `hostfetch --json` for that config.
+pub fn collect_with(cfg: &config::Config) -> SystemInfo {
+    let mut hostname = String::new();
+    let mut batteries = battery::get_batteries();
+    if cfg.battery.combine {
+        batteries = battery::combine(&batteries).into_iter().collect();
+    }
+
+    SystemInfo {
+        os: oschecker::get_os_info().ok(),
+        device: host::get_device_info(),
+        kernel: kernel::get_uname_data().ok(),
+        uptime: uptime::get_uptime().ok(),
+        loadavg: load_average::get_loadavg(),
+        cpu: cpu::get_cpu_info(),
+        gpus: gpu::get_gpus(),
+        memory: MemoryData::new(),
+        swap: swap::get_swap_info(),
+        disks: disk::get_disk_usage(&cfg.disk.mounts, &cfg.disk.hide_fs),
+        network: network::get_interfaces(&cfg.network.hide),
+        batteries,
+        packages: packages::get_package_counts(),
+        locale: locale::get_locale(),
+        desktop: desktop::get_desktop_info(),
+        shell: shell::get_shell_name(),
+        terminal: terminal::detect_terminal(),
+        user: username::get_username().ok(),
+        hostname: hostname::get_hostname(&mut hostname).ok().map(|()| hostname),
+    }
+}
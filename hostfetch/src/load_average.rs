@@ -1,41 +1,81 @@
-use std::process::Command;
 use std::fs;
+use std::fmt;
 
-pub fn get_loadavg() -> String {
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(r#"LC_ALL=C uptime 2>/dev/null | awk -F 'load average: ' '{print $2}' | tr -d ','"#)
-        .output();
-
-    if let Ok(output) = output {
-        if let Ok(mut output_str) = String::from_utf8(output.stdout) {
-            output_str = output_str.trim().to_string();
-            let parts: Vec<&str> = output_str.split_whitespace().take(3).collect();
-            
-            if parts.len() == 3 {
-                return format_load(&parts);
-            }
-        }
+use nix::sys::sysinfo::sysinfo;
+
+use crate::config::Config;
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError, Value};
+
+#[derive(Debug, Clone, Copy)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+impl fmt::Display for LoadAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2}, {:.2}, {:.2}", self.one, self.five, self.fifteen)
     }
+}
 
+pub fn get_loadavg() -> Option<LoadAverage> {
     if let Ok(content) = fs::read_to_string("/proc/loadavg") {
-        let parts: Vec<&str> = content.split_whitespace().take(3).collect();
-        if parts.len() == 3 {
-            return format_load(&parts);
+        let parts: Vec<f64> = content
+            .split_whitespace()
+            .take(3)
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        if let [one, five, fifteen] = parts[..] {
+            return Some(LoadAverage { one, five, fifteen });
         }
     }
 
-    "N/A".to_string()
+    sysinfo().ok().map(|info| {
+        let (one, five, fifteen) = info.load_average();
+        LoadAverage { one, five, fifteen }
+    })
 }
 
-fn format_load(parts: &[&str]) -> String {
-    let parse = |s: &str| s.trim().parse().unwrap_or_else(|_| {
-        s.chars().filter(|c| c.is_ascii_digit() || *c == '.').collect::<String>().parse().unwrap_or(0.0)
-    });
+pub struct LoadAverageModule;
+
+impl InfoModule for LoadAverageModule {
+    fn id(&self) -> &'static str {
+        "load_average"
+    }
+
+    fn label(&self) -> &'static str {
+        "Load Average"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{23f2}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let load = get_loadavg()
+            .ok_or_else(|| ModuleError::Unavailable("load average is unavailable".into()))?;
+
+        Ok(ModuleData::default()
+            .with("one", Value::Float(load.one))
+            .with("five", Value::Float(load.five))
+            .with("fifteen", Value::Float(load.fifteen)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        let field = |name| data.get(name).and_then(Value::as_f64).unwrap_or(0.0);
 
-    let one = parse(parts[0]);
-    let five = parse(parts[1]);
-    let fifteen = parse(parts[2]);
+        let load = LoadAverage {
+            one: field("one"),
+            five: field("five"),
+            fifteen: field("fifteen"),
+        };
 
-    format!("{:.2}, {:.2}, {:.2}", one, five, fifteen)
+        paint_info(&load.to_string(), cfg)
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["one", "five", "fifteen"]
+    }
 }
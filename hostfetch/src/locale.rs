@@ -1,20 +1,36 @@
-use std::process::Command;
-use std::io::{self, Error, ErrorKind};
-
-pub fn get_locale() -> io::Result<String> {
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(r#"echo $LANG"#)
-        .output()?;
-
-    if !output.status.success() {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        return Err(Error::new(ErrorKind::Other, error_msg.into_owned()));
+use std::env;
+
+use crate::module::{InfoModule, ModuleData, ModuleError};
+
+pub fn get_locale() -> Option<String> {
+    ["LC_ALL", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+}
+
+pub struct LocaleModule;
+
+impl InfoModule for LocaleModule {
+    fn id(&self) -> &'static str {
+        "locale"
     }
 
-    let locale = String::from_utf8_lossy(&output.stdout)
-        .trim()
-        .to_string();
+    fn label(&self) -> &'static str {
+        "Locale"
+    }
 
-    Ok(locale)
-}
\ No newline at end of file
+    fn icon(&self) -> &'static str {
+        "\u{f274}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let locale = get_locale()
+            .ok_or_else(|| ModuleError::Unavailable("LANG is not set".into()))?;
+
+        Ok(ModuleData::default().with("locale", locale))
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["locale"]
+    }
+}
@@ -0,0 +1,313 @@
+use std::fs;
+use std::path::Path;
+
+use colored::{Color, Colorize};
+
+use crate::config::{Config, LogoSource};
+use crate::image::{self, ImageLogo};
+use crate::{oschecker, terminal};
+
+pub struct Logo {
+    pub names: &'static [&'static str],
+    pub colors: &'static [Color],
+    pub art: &'static str,
+}
+
+// Art uses ${c1}..${c6} to switch to the logo's nth colour; a colour stays
+// active across lines until the next placeholder.
+pub const LOGOS: &[Logo] = &[
+    Logo {
+        names: &["arch", "archarm", "artix"],
+        colors: &[Color::Cyan],
+        art: r#"
+${c1}      /\
+     /  \
+    /\   \
+   /      \
+  /   ,,   \
+ /   |  |  -\
+/_-''    ''-_\
+"#,
+    },
+    Logo {
+        names: &["debian", "raspbian"],
+        colors: &[Color::Red],
+        art: r#"
+${c1}  _____
+ /  __ \
+|  /    |
+|  \___-
+-_
+  --_
+"#,
+    },
+    Logo {
+        names: &["ubuntu"],
+        colors: &[Color::Red, Color::White],
+        art: r#"
+${c1}         _
+     ---(_)
+ _/  ---  \
+(_) |   |
+  \  --- _/
+     ---(_)
+"#,
+    },
+    Logo {
+        names: &["pop"],
+        colors: &[Color::Cyan, Color::White],
+        art: r#"
+${c1}______
+\   _ \        __
+ \ \ \ \      / /
+  \ \_\ \    / /
+   \  ___\  /_/
+    \ \    _
+   __\_\__(_)_
+  (___________)
+"#,
+    },
+    Logo {
+        names: &["linuxmint", "mint"],
+        colors: &[Color::Green, Color::White],
+        art: r#"
+${c1} ___________
+|_          \
+  | ${c2}| _____ ${c1}|
+  | ${c2}| | | | ${c1}|
+  | ${c2}| | | | ${c1}|
+  | ${c2}\_____/ ${c1}|
+  \_________/
+"#,
+    },
+    Logo {
+        names: &["fedora", "rhel", "centos", "rocky", "almalinux"],
+        colors: &[Color::Blue],
+        art: r#"
+${c1}        ,'''''.
+       |   ,.  |
+       |  |  '_'
+  ,....|  |..
+.'  ,_;|   ..'
+|  |   |  |
+|  ',_,'  |
+ '.     ,'
+   '''''
+"#,
+    },
+    Logo {
+        names: &["opensuse", "opensuse-tumbleweed", "opensuse-leap", "suse"],
+        colors: &[Color::Green],
+        art: r#"
+${c1}  _______
+__|   __ \
+     / .\ \
+     \__/ |
+   _______|
+   \_______
+__________/
+"#,
+    },
+    Logo {
+        names: &["manjaro"],
+        colors: &[Color::Green],
+        art: r#"
+${c1}||||||||| ||||
+||||||||| ||||
+||||      ||||
+|||| |||| ||||
+|||| |||| ||||
+|||| |||| ||||
+|||| |||| ||||
+"#,
+    },
+    Logo {
+        names: &["endeavouros"],
+        colors: &[Color::Magenta, Color::Red, Color::Blue],
+        art: r#"
+${c1}          /${c2}\
+${c1}        /${c2}/  \${c3}\
+${c1}       /${c2}/    \ ${c3}\
+${c1}     / ${c2}/     _) ${c3})
+${c1}    /${c2}_/___-- ${c3}__-
+${c3}     /____--
+"#,
+    },
+    Logo {
+        names: &["gentoo"],
+        colors: &[Color::Magenta, Color::White],
+        art: r#"
+${c1} _-----_
+(       \
+\    0   \
+${c2} \        )
+ /      _/
+(     _-
+\____-
+"#,
+    },
+    Logo {
+        names: &["alpine"],
+        colors: &[Color::Blue],
+        art: r#"
+${c1}   /\ /\
+  // \  \
+ //   \  \
+///    \  \
+//      \  \
+         \
+"#,
+    },
+    Logo {
+        names: &["void"],
+        colors: &[Color::Green],
+        art: r#"
+${c1}    _______
+ _ \______ -
+| \  ___  \ |
+| | /   \ | |
+| | \___/ | |
+| \______ \_|
+ -_______\
+"#,
+    },
+    Logo {
+        names: &["nixos"],
+        colors: &[Color::Blue, Color::Cyan],
+        art: r#"
+${c1}  \\  \\ //
+ ==\\__\\/ //
+   //   \\//
+==//     //==
+ //\\___//
+// /\\  \\==
+  // \\  \\
+"#,
+    },
+    Logo {
+        names: &["android"],
+        colors: &[Color::Green],
+        art: r#"
+${c1}  ;,           ,;
+   ';,.-----.,;'
+  ,'           ',
+ /    O     O    \
+|                 |
+'-----------------'
+"#,
+    },
+    Logo {
+        names: &["linux"],
+        colors: &[Color::White, Color::BrightBlack, Color::Yellow],
+        art: r#"
+${c1}    ___
+   (${c2}.. ${c1}|
+   (${c3}<> ${c1}|
+  / ${c2}__  ${c1}\
+ ( ${c2}/  \ ${c1}/|
+${c3}_${c1}/\ ${c2}__)${c1}/${c3}_${c1})
+${c3}\/${c1}-____${c3}\/
+"#,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Logo> {
+    LOGOS.iter().find(|logo| logo.names.contains(&name))
+}
+
+pub fn detect() -> &'static Logo {
+    oschecker::get_os_ids()
+        .iter()
+        .find_map(|id| find(id))
+        .or_else(|| find("linux"))
+        .expect("the generic linux logo is bundled")
+}
+
+pub enum LogoArt {
+    Ascii(Vec<String>),
+    Image(ImageLogo),
+}
+
+// Image logos are only tried when the caller can show them (a colour
+// terminal); anything else falls back to the detected ASCII logo.
+pub fn load(cfg: &Config, allow_images: bool) -> LogoArt {
+    match cfg.logo() {
+        LogoSource::None => LogoArt::Ascii(Vec::new()),
+        LogoSource::Auto => LogoArt::Ascii(render_logo(detect())),
+        LogoSource::Named(name) => match find(name) {
+            Some(logo) => LogoArt::Ascii(render_logo(logo)),
+            None => {
+                eprintln!("Unknown logo: {}", name);
+                LogoArt::Ascii(render_logo(detect()))
+            }
+        },
+        LogoSource::File(path) if is_png(path) => {
+            let protocol = image::detect_protocol(&terminal::detect_terminal());
+            match protocol.filter(|_| allow_images) {
+                Some(protocol) => match image::load(path, protocol, cfg.logo_width()) {
+                    Ok(image) => LogoArt::Image(image),
+                    Err(e) => {
+                        eprintln!("Error reading logo {}: {}", path.display(), e);
+                        LogoArt::Ascii(render_logo(detect()))
+                    }
+                },
+                None => LogoArt::Ascii(render_logo(detect())),
+            }
+        }
+        LogoSource::File(path) => match fs::read_to_string(path) {
+            Ok(art) => LogoArt::Ascii(render(&art, detect().colors)),
+            Err(e) => {
+                eprintln!("Error reading logo {}: {}", path.display(), e);
+                LogoArt::Ascii(Vec::new())
+            }
+        },
+    }
+}
+
+fn is_png(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("png"))
+}
+
+fn render_logo(logo: &Logo) -> Vec<String> {
+    render(logo.art.trim_start_matches('\n'), logo.colors)
+}
+
+pub fn render(art: &str, colors: &[Color]) -> Vec<String> {
+    let mut current = None;
+
+    art.lines()
+        .map(|line| {
+            let mut output = String::new();
+            let mut rest = line;
+            while let Some(start) = rest.find("${c") {
+                output.push_str(&paint(&rest[..start], current));
+                rest = &rest[start..];
+
+                let index = rest[3..]
+                    .split_once('}')
+                    .and_then(|(index, _)| index.parse::<usize>().ok())
+                    .filter(|&index| index > 0);
+                match index {
+                    Some(index) => {
+                        current = colors.get(index - 1).or(colors.last()).copied();
+                        rest = &rest[rest.find('}').unwrap_or(0) + 1..];
+                    }
+                    None => {
+                        output.push_str(&paint("${c", current));
+                        rest = &rest[3..];
+                    }
+                }
+            }
+            output.push_str(&paint(rest, current));
+            output
+        })
+        .collect()
+}
+
+fn paint(text: &str, color: Option<Color>) -> String {
+    match color {
+        Some(color) if !text.is_empty() => text.color(color).bold().to_string(),
+        _ => text.to_string(),
+    }
+}
@@ -1,61 +1,67 @@
-mod config;
-mod hostname;
-mod username;
-mod oschecker;
-mod host;
-mod kernel;
-mod uptime;
-mod load_average;
-mod ram;
-mod swap;
-mod terminal;
-mod shell;
-mod locale;
+mod cli;
 
+use clap::Parser;
+use cli::{Cli, Command, ConfigAction, Format};
 use colored::Colorize;
-use config::{load_or_create, Stylize};
-use host::get_device_info;
+use hostfetch::config::{self, load_config, Config, LabelAlign, LogoPosition, OnError, Stylize};
+use hostfetch::image::ImageLogo;
+use hostfetch::logo::LogoArt;
+use hostfetch::module::{paint_info, render_module, InfoModule, ModuleData, ModuleError, Registry};
+use hostfetch::width::display_width;
+use hostfetch::{check, edit, hostname, logo, username};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
 
 lazy_static! {
     static ref ANSI_ESCAPE: Regex = Regex::new(r"\x1B\[[0-9;]*[a-zA-Z]").unwrap();
 }
 
-fn visible_length(s: &str) -> usize {
-    ANSI_ESCAPE.replace_all(s, "").chars().count()
+fn strip_ansi(s: &str) -> String {
+    ANSI_ESCAPE.replace_all(s, "").into_owned()
 }
 
-fn draw_border(lines: &[String], color: colored::Color) {
+fn visible_length(s: &str, icon_width: usize) -> usize {
+    display_width(&strip_ansi(s), icon_width)
+}
+
+fn draw_border(lines: &[String], color: colored::Color, icon_width: usize) -> Vec<String> {
     if lines.is_empty() {
-        return;
+        return Vec::new();
     }
 
     let max_length = lines
         .iter()
-        .map(|line| visible_length(line))
+        .map(|line| visible_length(line, icon_width))
         .max()
         .unwrap_or(0);
 
     let top = format!("╭─{}─╮", "─".repeat(max_length)).color(color);
     let bottom = format!("╰─{}─╯", "─".repeat(max_length)).color(color);
 
-    println!("{}", top);
+    let mut output = vec![top.to_string()];
     for line in lines {
-        let padding = max_length - visible_length(line);
-        println!(
+        let padding = max_length - visible_length(line, icon_width);
+        output.push(format!(
             "{} {}{} {}",
             "│".color(color),
             line,
             " ".repeat(padding),
             "│".color(color)
-        );
+        ));
     }
-    println!("{}", bottom);
+    output.push(bottom.to_string());
+    output
 }
 
-fn draw_centered_border(content: &str, color: colored::Color, max_width: usize) {
-    let content_len = visible_length(content);
+fn draw_centered_border(
+    content: &str,
+    color: colored::Color,
+    max_width: usize,
+    icon_width: usize,
+) -> Vec<String> {
+    let content_len = visible_length(content, icon_width);
     let padding = (max_width.saturating_sub(content_len)) / 2;
     let line = format!(
         "{}{}{}",
@@ -63,251 +69,288 @@ fn draw_centered_border(content: &str, color: colored::Color, max_width: usize)
         content,
         " ".repeat(max_width - content_len - padding)
     );
-    draw_border(&[line], color);
+    draw_border(&[line], color, icon_width)
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cfg = load_or_create()?;
-    let mut all_lines = Vec::new();
-
-    //icons
-
-    let os_icon = if cfg.icons_enabled() {
-        "\u{f31a} "
-    } else {
-        ""
-    };
-
-    let host_icon = if cfg.icons_enabled() {
-        "\u{f109} "
-    } else {
-        ""
-    };
-
-    let terminal_icon = if cfg.icons_enabled() {
-        "\u{f489} "
-    } else {
-        ""
-    };
-
-    let shell_icon = if cfg.icons_enabled() {
-        "\u{e691} "
-    } else {
-        ""
-    };
-
-    let kernel_icon = if cfg.icons_enabled() {
-        "\u{f013} "
-    } else {
-        ""
-    };
+// The image is drawn into blank lines reserved beforehand, so the cursor
+// can be moved back up to its top-left corner without the terminal
+// scrolling underneath it.
+fn print_with_image(image: &ImageLogo, boxes: &[String], position: LogoPosition) {
+    print!("{}\x1b[{}A\x1b7{}\x1b8", "\n".repeat(image.rows), image.rows, image.escape);
 
-    let uptime_icon = if cfg.icons_enabled() {
-        "\u{f43a} "
-    } else {
-        ""
-    };
+    match position {
+        LogoPosition::Top => {
+            print!("{}", "\n".repeat(image.rows + 1));
+            boxes.iter().for_each(|line| println!("{}", line));
+        }
+        LogoPosition::Left => {
+            for line in boxes {
+                println!("\x1b[{}C{}", image.columns + 2, line);
+            }
+            print!("{}", "\n".repeat(image.rows.saturating_sub(boxes.len())));
+        }
+    }
+}
 
-    let load_average_icon = if cfg.icons_enabled() {
-        "\u{23f2} "
-    } else {
-        ""
-    };
+fn print_with_logo(logo: &[String], boxes: &[String], position: LogoPosition) {
+    if logo.is_empty() {
+        boxes.iter().for_each(|line| println!("{}", line));
+        return;
+    }
 
-    let ram_icon = if cfg.icons_enabled() {
-        "\u{f035b} "
-    } else {
-        ""
-    };
+    match position {
+        LogoPosition::Top => {
+            for line in logo {
+                println!("{}", line);
+            }
+            println!();
+            boxes.iter().for_each(|line| println!("{}", line));
+        }
+        LogoPosition::Left => {
+            let width = logo.iter().map(|line| visible_length(line, 1)).max().unwrap_or(0);
+            for i in 0..logo.len().max(boxes.len()) {
+                let art = logo.get(i).map(String::as_str).unwrap_or("");
+                match boxes.get(i) {
+                    Some(line) => println!(
+                        "{}{}  {}",
+                        art,
+                        " ".repeat(width - visible_length(art, 1)),
+                        line
+                    ),
+                    None => println!("{}", art),
+                }
+            }
+        }
+    }
+}
 
-    let swap_icon = if cfg.icons_enabled() {
-        "\u{ebcb} "
-    } else {
-        ""
-    };
+fn label_width(modules: &[&dyn InfoModule]) -> usize {
+    modules
+        .iter()
+        .map(|module| display_width(module.label(), 1) + 1)
+        .max()
+        .unwrap_or(0)
+}
 
-    let locale_icon = if cfg.icons_enabled() {
-        "\u{f274} "
+fn module_line(module: &dyn InfoModule, value: &str, cfg: &Config, label_width: usize) -> String {
+    let icon = if cfg.icons_enabled() {
+        format!("{} ", module.icon())
     } else {
-        ""
+        String::new()
     };
 
-    //names
-
-    let os_name = if cfg.names_enabled() {
-        "OS:"
+    let (indent, name, space) = if cfg.names_enabled() {
+        let name = format!("{}:", module.label());
+        let padding = " ".repeat(label_width.saturating_sub(display_width(&name, 1)));
+        match cfg.label_align() {
+            LabelAlign::Left => (String::new(), name, padding + " "),
+            LabelAlign::Right => (padding, name, " ".to_string()),
+        }
     } else {
-        ":"
+        (String::new(), ":".to_string(), " ".to_string())
     };
 
-    let host_name = if cfg.names_enabled() {
-        "Host:"
-    } else {
-        ":"
-    };
+    format!(
+        "{}{}{}{}{}",
+        icon.color(cfg.get_icon_color()),
+        indent,
+        name.color(cfg.get_name_color()).style(cfg.get_name_styles()),
+        space,
+        value
+    )
+}
 
-    let terminal_name = if cfg.names_enabled() {
-        "Terminal:"
-    } else {
-        ":"
-    };
+fn print_json(
+    username: &str,
+    hostname: Option<&str>,
+    collected: &[(&dyn InfoModule, Result<ModuleData, ModuleError>)],
+) -> Result<(), serde_json::Error> {
+    let mut modules = serde_json::Map::new();
+    let mut errors = serde_json::Map::new();
+    for (module, result) in collected {
+        let value = match result {
+            Ok(data) => serde_json::to_value(data)?,
+            Err(e) => {
+                errors.insert(module.id().to_string(), e.to_string().into());
+                serde_json::Value::Null
+            }
+        };
+        modules.insert(module.id().to_string(), value);
+    }
 
-    let shell_name = if cfg.names_enabled() {
-        "Shell:"
-    } else {
-        ":"
-    };
+    let report = serde_json::json!({
+        "user": username,
+        "hostname": hostname,
+        "modules": modules,
+        "errors": errors,
+    });
 
-    let kernel_name = if cfg.names_enabled() {
-        "Kernel:"
-    } else {
-        ":"
-    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
 
-    let uptime_name = if cfg.names_enabled() {
-        "Uptime:"
-    } else {
-        ":"
+fn placeholder(e: &ModuleError, cfg: &Config) -> Option<String> {
+    let text = match cfg.on_error() {
+        OnError::Hide => return None,
+        OnError::Na if matches!(e, ModuleError::Timeout(_)) => "timeout".to_string(),
+        OnError::Na => "N/A".to_string(),
+        OnError::Error => e.to_string(),
     };
+    Some(paint_info(&text, cfg))
+}
 
-    let load_average_name = if cfg.names_enabled() {
-        "Load Average:"
-    } else {
-        ":"
-    };
+fn run_config(action: &ConfigAction, cli: &Cli, cfg: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ConfigAction::Path => println!("{}", config::config_path(cli.config.as_deref())?.display()),
+        ConfigAction::Show => print!("{}", toml::to_string_pretty(cfg)?),
+        ConfigAction::Get { key } => match edit::get(cfg, key)? {
+            Some(value) => println!("{}", value),
+            None => return Err(format!("{} is not set", key).into()),
+        },
+        ConfigAction::List => {
+            for (key, value) in edit::list(cfg)? {
+                println!("{} = {}", key, value);
+            }
+        }
+        ConfigAction::Check
+        | ConfigAction::Init { .. }
+        | ConfigAction::Set { .. }
+        | ConfigAction::Unset { .. } => {}
+    }
+    Ok(())
+}
 
-    let ram_name = if cfg.names_enabled() {
-        "RAM:"
-    } else {
-        ":"
-    };
+// Actions on the files themselves run before the config is loaded, so they
+// also work when a file does not parse.
+fn edit_config(action: &ConfigAction, cli: &Cli) -> Result<Option<ExitCode>, Box<dyn std::error::Error>> {
+    let path = config::config_path(cli.config.as_deref())?;
+    let registry = Registry::builtin(&Config::default());
+    match action {
+        ConfigAction::Check => return check_config(cli, &registry).map(Some),
+        ConfigAction::Init { force } => {
+            config::init(&path, *force)?;
+            println!("Wrote {}", path.display());
+        }
+        ConfigAction::Set { key, value } => edit::set(&path, key, value, &registry)?,
+        ConfigAction::Unset { key } => edit::unset(&path, key, &registry)?,
+        _ => return Ok(None),
+    }
+    Ok(Some(ExitCode::SUCCESS))
+}
 
-    let swap_name = if cfg.names_enabled() {
-        "Swap:"
-    } else {
-        ":"
-    };
+fn check_config(cli: &Cli, registry: &Registry) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let files = config::config_files(cli.config.as_deref())?;
+    if files.is_empty() {
+        println!("no config file found, using the built-in defaults");
+    }
 
-    let locale_name = if cfg.names_enabled() {
-        "Locale:"
-    } else {
-        ":"
-    };
+    let mut problems = false;
+    for path in files {
+        let diagnostics = check::check_file(&path, registry)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        for diagnostic in &diagnostics {
+            println!("{}", diagnostic);
+        }
+        if diagnostics.is_empty() {
+            println!("{}: no problems found", path.display());
+        }
+        problems |= !diagnostics.is_empty();
+    }
 
-    //space
+    if problems {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
 
-    let os_space = if cfg.names_enabled() {
-        "              "
-    } else {
-        " "
-    };
+fn list_modules(registry: &Registry, cfg: &Config) {
+    let enabled = cfg.module_order();
+    for module in registry.iter() {
+        let marker = if enabled.iter().any(|id| id == module.id()) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{} {:<14} {:<14} {}",
+            marker,
+            module.id(),
+            module.label(),
+            module.placeholders().join(" ")
+        );
+    }
+}
 
-    let host_space = if cfg.names_enabled() {
-        "            "
-    } else {
-        " "
-    };
+fn apply_overrides(cli: &Cli, cfg: &mut Config, registry: &Registry) {
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
 
-    let terminal_space = if cfg.names_enabled() {
-        "        "
-    } else {
-        " "
-    };
+    if cli.no_icons {
+        cfg.icons.enabled = false;
+    }
 
-    let shell_space = if cfg.names_enabled() {
-        "           "
-    } else {
-        " "
-    };
+    for id in cli.only.iter().chain(&cli.hide) {
+        if registry.get(id).is_none() {
+            eprintln!("Unknown module: {}", id);
+        }
+    }
 
-    let kernel_space = if cfg.names_enabled() {
-        "          "
+    let mut order = if cli.only.is_empty() {
+        cfg.module_order()
     } else {
-        " "
+        cli.only.clone()
     };
+    order.retain(|id| !cli.hide.contains(id));
+    cfg.set_module_order(order);
+}
 
-    let uptime_space = if cfg.names_enabled() {
-        "          "
-    } else {
-        " "
-    };
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-    let load_average_space = if cfg.names_enabled() {
-        "    "
-    } else {
-        " "
-    };
+fn run() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    if let Some(Command::Config { action }) = &cli.command
+        && let Some(code) = edit_config(action, &cli)?
+    {
+        return Ok(code);
+    }
 
-    let ram_space = if cfg.names_enabled() {
-        "             "
-    } else {
-        " "
-    };
+    let mut cfg = load_config(cli.config.as_deref())?;
+    let registry = Registry::builtin(&cfg);
 
-    let swap_space = if cfg.names_enabled() {
-        "            "
-    } else {
-        " "
-    };
+    for path in config::config_files(cli.config.as_deref())? {
+        for diagnostic in check::check_file(&path, &registry).unwrap_or_default() {
+            eprintln!("{}", diagnostic);
+        }
+    }
 
-    let locale_space = if cfg.names_enabled() {
-        "          "
-    } else {
-        " "
-    };
+    match &cli.command {
+        Some(Command::Config { action }) => {
+            run_config(action, &cli, &cfg)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(Command::Modules) => {
+            list_modules(&registry, &cfg);
+            return Ok(ExitCode::SUCCESS);
+        }
+        None => {}
+    }
 
-    let host = get_device_info();
+    apply_overrides(&cli, &mut cfg, &registry);
+    let json = cli.format() == Format::Json;
+    let mut all_lines = Vec::new();
     let mut my_host = String::new();
 
-    let name_color = cfg.get_name_color();
-    let name_style = cfg.get_name_styles();
-    let info_color = cfg.get_info_color();
-    let info_style = cfg.get_info_styles();
     let host_color = cfg.get_host_color();
     let host_style = cfg.get_host_styles();
-    let icon_color = cfg.get_icon_color();
-
-    let os_info: String = oschecker::get_os_info()?;
-    let uptime_result = uptime::get_uptime();
-    let load_info = load_average::get_loadavg();
-    let mem = ram::MemoryData::new();
-    let ram_usage = mem.formatted_usage();
-    let ram_percent = mem.formatted_percent();
-    let swap_data = swap::get_swap_info();
-    let terminal = terminal::detect_terminal();
-    let shell_info = shell::get_shell_name();
-    let locale_result = locale::get_locale();
-
-    let (swap_usage, swap_percent) = match swap_data {
-        Some(data) => data,
-        None => {
-            ("No swap".to_string(), "(0%)".to_string())
-        }
-    };
-
-    let uptime = match uptime_result {
-        Ok(value) => value,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(e.into());
-        }
-    };
-
-    let locale = match locale_result {
-        Ok(value) => value,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(e.into());
-        }
-    };
-
-    let (uname_data, kernel_data) = match kernel::get_uname_data() {
-        Ok(data) => (data.uname, data.kernel_version),
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            ("Unknown".into(), "Unknown".into())
-        }
-    };
 
     let username = match username::get_username() {
         Ok(name) => name,
@@ -317,124 +360,72 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    match hostname::get_hostname(&mut my_host) {
-        Ok(()) => {
-            let user_host = format!(
-                "{}@{}",
-                username.color(host_color).style(host_style),
-                my_host.color(host_color).style(host_style)
-            );
-            all_lines.push(user_host);
-        },
-        Err(e) => eprintln!("Error getting hostname: {}", e),
-    }
+    let hostname_result = hostname::get_hostname(&mut my_host);
 
-    let os_line = format!(
-        "{}{}{}{}",
-        os_icon.color(icon_color),
-        os_name.color(name_color).style(name_style),
-        os_space,
-        os_info.color(info_color).style(info_style)
-    );            
-
-    let host_line = format!(
-        "{}{}{}{}",
-        host_icon.color(icon_color),
-        host_name.color(name_color).style(name_style),
-        host_space,
-        host.color(info_color).style(info_style)
-    );
-
-    let terminal_line = format!(
-        "{}{}{}{}",
-        terminal_icon.color(icon_color),
-        terminal_name.color(name_color).style(name_style),
-        terminal_space,
-        terminal.color(info_color).style(info_style)
-    );
+    let collected = registry.collect(&cfg.module_order(), |id| cfg.module_timeout(id));
 
-    let shell_line = format!(
-        "{}{}{}{}",
-        shell_icon.color(icon_color),
-        shell_name.color(name_color).style(name_style),
-        shell_space,
-        shell_info.color(info_color).style(info_style),
-    );
-
-    let kernel_line = format!(
-        "{}{}{}{} {}",
-        kernel_icon.color(icon_color),
-        kernel_name.color(name_color).style(name_style),
-        kernel_space,
-        uname_data.color(info_color).style(info_style),
-        kernel_data.color(info_color).style(info_style)
-    );          
-
-    let uptime_line = format!(
-        "{}{}{}{}",
-        uptime_icon.color(icon_color),
-        uptime_name.color(name_color).style(name_style),
-        uptime_space,
-        uptime.color(info_color).style(info_style)
-    );
-
-    let load_average_line = format!(
-        "{}{}{}{}",
-        load_average_icon.color(icon_color),
-        load_average_name.color(name_color).style(name_style),
-        load_average_space,
-        load_info.color(info_color).style(info_style)
-    );
+    let mut failed = false;
+    for (module, result) in &collected {
+        if let Err(e) = result {
+            failed = true;
+            if cli.strict {
+                eprintln!("Error in {}: {}", module.id(), e);
+            }
+        }
+    }
+    let exit_code = if cli.strict && failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    };
 
-    let ram_line = format!(
-        "{}{}{}{} ({})",
-        ram_icon.color(icon_color),
-        ram_name.color(name_color).style(name_style),
-        ram_space,
-        ram_usage.color(info_color).style(info_style),
-        ram_percent
-    );
+    if json {
+        let hostname = hostname_result.is_ok().then_some(my_host.as_str());
+        print_json(&username, hostname, &collected)?;
+        return Ok(exit_code);
+    }
 
-    let swap_line = format!(
-        "{}{}{}{} ({})",
-        swap_icon.color(icon_color),
-        swap_name.color(name_color).style(name_style),
-        swap_space,
-        swap_usage.color(info_color).style(info_style),
-        swap_percent
+    // The header is always the first line, so a failed lookup gets a
+    // placeholder like the username does.
+    if let Err(e) = hostname_result {
+        eprintln!("Error getting hostname: {}", e);
+        my_host = "unknown".to_string();
+    }
+    let user_host = format!(
+        "{}@{}",
+        username.color(host_color).style(host_style),
+        my_host.color(host_color).style(host_style)
     );
+    all_lines.push(user_host);
 
-    let locale_line = format!(
-        "{}{}{}{}",
-        locale_icon.color(icon_color),
-        locale_name.color(name_color).style(name_style),
-        locale_space,
-        locale.color(info_color).style(info_style)
-    );
+    let rendered: Vec<(&dyn InfoModule, Vec<String>)> = collected
+        .iter()
+        .map(|(module, result)| {
+            let values = match result {
+                Ok(data) => render_module(*module, data, &cfg),
+                Err(e) => placeholder(e, &cfg).into_iter().collect(),
+            };
+            (*module, values)
+        })
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+
+    let shown: Vec<&dyn InfoModule> = rendered.iter().map(|(module, _)| *module).collect();
+    let label_width = label_width(&shown);
+    for (module, values) in &rendered {
+        for value in values {
+            all_lines.push(module_line(*module, value, &cfg, label_width));
+        }
+    }
 
-    let mut items = vec![
-        (cfg.position.host_order, host_line),
-        (cfg.position.os_order, os_line),
-        (cfg.position.terminal_order, terminal_line),
-        (cfg.position.shell_order, shell_line),
-        (cfg.position.kernel_order, kernel_line),
-        (cfg.position.uptime_order, uptime_line),
-        (cfg.position.load_average_order, load_average_line),
-        (cfg.position.ram_order, ram_line),
-        (cfg.position.swap_order, swap_line),
-        (cfg.position.locale_order, locale_line),
-    ];
-
-    items.retain(|(order, _)| *order > 0);
-    items.sort_by_key(|(order, _)| *order);
-
-    for (_, line) in items {
-        all_lines.push(line);
+    if cli.no_color {
+        all_lines = all_lines.iter().map(|line| strip_ansi(line)).collect();
     }
 
+    let icon_width = cfg.icon_width();
     let max_length = all_lines
         .iter()
-        .map(|line| visible_length(line))
+        .map(|line| visible_length(line, icon_width))
         .max()
         .unwrap_or(0);
 
@@ -444,16 +435,26 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let border_color = cfg.border_color();
+    let mut boxes = Vec::new();
 
     if !all_lines.is_empty() {
         let user_host = all_lines[0].clone();
-        draw_centered_border(&user_host, host_color, max_length);
+        boxes.extend(draw_centered_border(&user_host, host_color, max_length, icon_width));
     }
 
     if !system_lines.is_empty() {
-        draw_border(&system_lines, border_color);
+        boxes.extend(draw_border(&system_lines, border_color, icon_width));
     }
 
+    match logo::load(&cfg, !cli.no_color && io::stdout().is_terminal()) {
+        LogoArt::Image(image) => print_with_image(&image, &boxes, cfg.logo_position()),
+        LogoArt::Ascii(mut logo) => {
+            if cli.no_color {
+                logo = logo.iter().map(|line| strip_ansi(line)).collect();
+            }
+            print_with_logo(&logo, &boxes, cfg.logo_position());
+        }
+    }
 
-    Ok(())
+    Ok(exit_code)
 }
@@ -0,0 +1,453 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use owo_colors::{OwoColorize, Style};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::config::{Config, Stylize};
+use crate::{
+    battery, cpu, desktop, disk, gpu, host, kernel, load_average, locale, network, oschecker, packages,
+    ram, shell, swap, template, terminal, uptime,
+};
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
+pub enum Value {
+    Text(String),
+    Integer(u64),
+    Float(f64),
+    Bytes(u64),
+    Percent(f64),
+    List(Vec<ModuleData>),
+}
+
+impl Value {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Integer(value) | Value::Bytes(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(value) | Value::Percent(value) => Some(*value),
+            Value::Integer(value) | Value::Bytes(value) => Some(*value as f64),
+            Value::Text(_) | Value::List(_) => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[ModuleData]> {
+        match self {
+            Value::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Text(value) => write!(f, "{}", value),
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{:.2}", value),
+            Value::Bytes(value) => write!(f, "{}", format_bytes(*value)),
+            Value::Percent(value) => write!(f, "{:.0}%", value),
+            Value::List(items) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| item.values().map(Value::to_string).collect::<Vec<_>>().join(" "))
+                    .collect();
+                write!(f, "{}", items.join(", "))
+            }
+        }
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ModuleData {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl ModuleData {
+    pub fn with(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+
+    pub fn set(&mut self, name: &'static str, value: impl Into<Value>) {
+        let value = value.into();
+        match self.fields.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((name, value)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn text(&self, name: &str) -> String {
+        self.get(name).map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.fields.iter().map(|(_, value)| value)
+    }
+}
+
+impl Serialize for ModuleData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for (name, value) in &self.fields {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+pub trait InfoModule: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    fn label(&self) -> &'static str;
+
+    fn icon(&self) -> &'static str;
+
+    fn collect(&self) -> Result<ModuleData, ModuleError>;
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        data.values()
+            .map(|value| paint_info(&value.to_string(), cfg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn render_lines(&self, data: &ModuleData, cfg: &Config) -> Vec<String> {
+        vec![self.render(data, cfg)]
+    }
+
+    // Names a `[modules.formats]` template may use for this module.
+    fn placeholders(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // Values for the placeholders, one set per output line.
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        vec![data.clone()]
+    }
+}
+
+pub fn render_module(module: &dyn InfoModule, data: &ModuleData, cfg: &Config) -> Vec<String> {
+    match cfg.module_format(module.id()) {
+        Some(format) if format_problem(module, format).is_none() => module
+            .template_values(data, cfg)
+            .iter()
+            .map(|values| paint_info(&template::render(format, values), cfg))
+            .collect(),
+        _ => module.render_lines(data, cfg),
+    }
+}
+
+// Why a format cannot be used for the module: a syntax error or a
+// placeholder the module does not provide.
+pub fn format_problem(module: &dyn InfoModule, format: &str) -> Option<String> {
+    let placeholders = match template::placeholders(format) {
+        Ok(placeholders) => placeholders,
+        Err(e) => return Some(e),
+    };
+    placeholders
+        .iter()
+        .find(|name| !module.placeholders().contains(name))
+        .map(|unknown| {
+            format!(
+                "unknown placeholder {{{}}} (available: {})",
+                unknown,
+                module.placeholders().join(", ")
+            )
+        })
+}
+
+// The items of a list field, for modules that print one line per item.
+pub fn list_items(data: &ModuleData, name: &str) -> Vec<ModuleData> {
+    data.get(name)
+        .and_then(Value::as_list)
+        .unwrap_or_default()
+        .to_vec()
+}
+
+pub fn paint_info(text: &str, cfg: &Config) -> String {
+    text.color(cfg.get_info_color())
+        .style(cfg.get_info_styles())
+        .to_string()
+}
+
+pub fn paint_percent(percent: f64) -> String {
+    let percent_value = format!("{:.0}%", percent);
+
+    let bold_style = Style::new().bold();
+
+    if percent <= 50.0 {
+        percent_value
+            .style(bold_style
+                .green())
+            .to_string()
+    } else if percent <= 75.0 {
+        percent_value
+            .style(bold_style
+                .yellow())
+            .to_string()
+    } else {
+        percent_value
+            .style(bold_style
+                .red())
+            .to_string()
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if size.fract() == 0.0 || unit_index == 0 {
+        format!("{:.0} {}", size, UNITS[unit_index])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit_index])
+    }
+}
+
+#[derive(Debug)]
+pub enum ModuleError {
+    Io(io::Error),
+    Unavailable(String),
+    Timeout(Duration),
+    Panicked,
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModuleError::Io(e) => write!(f, "{}", e),
+            ModuleError::Unavailable(reason) => write!(f, "{}", reason),
+            ModuleError::Timeout(limit) => write!(f, "timed out after {} ms", limit.as_millis()),
+            ModuleError::Panicked => write!(f, "module panicked"),
+        }
+    }
+}
+
+impl Error for ModuleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ModuleError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ModuleError {
+    fn from(e: io::Error) -> Self {
+        ModuleError::Io(e)
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    modules: Vec<Arc<dyn InfoModule>>,
+}
+
+impl Registry {
+    pub fn builtin(cfg: &Config) -> Self {
+        let mut registry = Self::default();
+        registry.register(oschecker::OsModule);
+        registry.register(host::HostModule);
+        registry.register(desktop::DesktopModule);
+        registry.register(terminal::TerminalModule);
+        registry.register(shell::ShellModule);
+        registry.register(kernel::KernelModule);
+        registry.register(packages::PackagesModule);
+        registry.register(uptime::UptimeModule);
+        registry.register(load_average::LoadAverageModule);
+        registry.register(cpu::CpuModule::new(cfg));
+        registry.register(gpu::GpuModule);
+        registry.register(ram::RamModule);
+        registry.register(swap::SwapModule);
+        registry.register(disk::DiskModule::new(cfg));
+        registry.register(network::NetworkModule::new(cfg));
+        registry.register(battery::BatteryModule::new(cfg));
+        registry.register(locale::LocaleModule);
+        registry
+    }
+
+    pub fn register(&mut self, module: impl InfoModule + 'static) {
+        self.modules.retain(|existing| existing.id() != module.id());
+        self.modules.push(Arc::new(module));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn InfoModule> {
+        self.modules.iter().map(|module| module.as_ref())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn InfoModule> {
+        self.modules
+            .iter()
+            .find(|module| module.id() == id)
+            .map(|module| module.as_ref())
+    }
+
+    // Runs every requested module on its own thread. A module that misses its
+    // deadline is reported as timed out and left to finish in the background.
+    pub fn collect(
+        &self,
+        ids: &[String],
+        timeout: impl Fn(&str) -> Duration,
+    ) -> Vec<(&dyn InfoModule, Result<ModuleData, ModuleError>)> {
+        let modules: Vec<&Arc<dyn InfoModule>> = ids
+            .iter()
+            .filter_map(|id| self.modules.iter().find(|module| module.id() == id))
+            .collect();
+
+        let start = Instant::now();
+        let (tx, rx) = mpsc::channel();
+
+        for (index, module) in modules.iter().enumerate() {
+            let tx = tx.clone();
+            let module = Arc::clone(module);
+            thread::spawn(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| module.collect()))
+                    .unwrap_or(Err(ModuleError::Panicked));
+                let _ = tx.send((index, result));
+            });
+        }
+        drop(tx);
+
+        let limits: Vec<Duration> = modules.iter().map(|module| timeout(module.id())).collect();
+        let mut results: Vec<Option<Result<ModuleData, ModuleError>>> =
+            modules.iter().map(|_| None).collect();
+
+        loop {
+            let now = Instant::now();
+            for (index, result) in results.iter_mut().enumerate() {
+                if result.is_none() && start + limits[index] <= now {
+                    *result = Some(Err(ModuleError::Timeout(limits[index])));
+                }
+            }
+
+            let next_deadline = results
+                .iter()
+                .zip(&limits)
+                .filter(|(result, _)| result.is_none())
+                .map(|(_, limit)| start + *limit)
+                .min();
+
+            let Some(deadline) = next_deadline else {
+                break;
+            };
+
+            match rx.recv_timeout(deadline.saturating_duration_since(now)) {
+                Ok((index, result)) => {
+                    if results[index].is_none() {
+                        results[index] = Some(result);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        modules
+            .into_iter()
+            .zip(results)
+            .map(|(module, result)| {
+                (module.as_ref(), result.unwrap_or(Err(ModuleError::Panicked)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Panics;
+
+    impl InfoModule for Panics {
+        fn id(&self) -> &'static str {
+            "panics"
+        }
+
+        fn label(&self) -> &'static str {
+            "Panics"
+        }
+
+        fn icon(&self) -> &'static str {
+            ""
+        }
+
+        fn collect(&self) -> Result<ModuleData, ModuleError> {
+            panic!("collect failed")
+        }
+    }
+
+    struct Slow;
+
+    impl InfoModule for Slow {
+        fn id(&self) -> &'static str {
+            "slow"
+        }
+
+        fn label(&self) -> &'static str {
+            "Slow"
+        }
+
+        fn icon(&self) -> &'static str {
+            ""
+        }
+
+        fn collect(&self) -> Result<ModuleData, ModuleError> {
+            thread::sleep(Duration::from_millis(200));
+            Ok(ModuleData::default().with("value", "done"))
+        }
+    }
+
+    #[test]
+    fn panic_is_reported_without_waiting_for_the_deadline() {
+        let mut registry = Registry::default();
+        registry.register(Panics);
+        registry.register(Slow);
+
+        let start = Instant::now();
+        let ids = ["panics".to_string(), "slow".to_string()];
+        let results = registry.collect(&ids, |_| Duration::from_secs(5));
+
+        assert!(matches!(results[0].1, Err(ModuleError::Panicked)));
+        assert!(results[1].1.is_ok());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}
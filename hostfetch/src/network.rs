@@ -0,0 +1,309 @@
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+
+use nix::ifaddrs::getifaddrs;
+use nix::net::if_::InterfaceFlags;
+use nix::sys::socket::SockaddrStorage;
+
+use crate::config::Config;
+use crate::module::{list_items, paint_info, InfoModule, ModuleData, ModuleError, Value};
+
+const SYSFS_NET: &str = "/sys/class/net";
+
+#[derive(Debug, Clone)]
+pub struct InterfaceAddress {
+    pub address: IpAddr,
+    pub prefix: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub addresses: Vec<InterfaceAddress>,
+    pub mac: Option<String>,
+    pub speed_mbps: Option<u64>,
+    pub state: Option<String>,
+}
+
+pub fn get_interfaces(hide: &[String]) -> Vec<NetworkInterface> {
+    let Ok(addrs) = getifaddrs() else {
+        return Vec::new();
+    };
+
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+    for ifaddr in addrs {
+        if !ifaddr.flags.contains(InterfaceFlags::IFF_UP)
+            || hide.iter().any(|pattern| matches_pattern(pattern, &ifaddr.interface_name))
+        {
+            continue;
+        }
+
+        let Some(address) = ifaddr.address.as_ref().and_then(ip_address) else {
+            continue;
+        };
+        let prefix = ifaddr
+            .netmask
+            .as_ref()
+            .and_then(ip_address)
+            .map(|mask| match mask {
+                IpAddr::V4(mask) => mask.to_bits().count_ones(),
+                IpAddr::V6(mask) => mask.to_bits().count_ones(),
+            })
+            .unwrap_or(0);
+
+        let index = match interfaces.iter().position(|i| i.name == ifaddr.interface_name) {
+            Some(index) => index,
+            None => {
+                interfaces.push(read_link_info(&ifaddr.interface_name));
+                interfaces.len() - 1
+            }
+        };
+        interfaces[index].addresses.push(InterfaceAddress { address, prefix });
+    }
+
+    interfaces
+}
+
+fn ip_address(addr: &SockaddrStorage) -> Option<IpAddr> {
+    if let Some(addr) = addr.as_sockaddr_in() {
+        return Some(IpAddr::V4(addr.ip()));
+    }
+    addr.as_sockaddr_in6().map(|addr| IpAddr::V6(addr.ip()))
+}
+
+fn read_link_info(name: &str) -> NetworkInterface {
+    let dir = Path::new(SYSFS_NET).join(name);
+    let read = |file| {
+        fs::read_to_string(dir.join(file))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    NetworkInterface {
+        name: name.to_string(),
+        addresses: Vec::new(),
+        mac: read("address").filter(|mac| mac != "00:00:00:00:00:00"),
+        // Virtual and disconnected links report -1 here.
+        speed_mbps: read("speed").and_then(|speed| speed.parse::<u64>().ok()),
+        state: read("operstate"),
+    }
+}
+
+// Shell-style matching where '*' stands for any run of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(tail) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            tail.char_indices()
+                .map(|(index, _)| index)
+                .chain([tail.len()])
+                .any(|index| matches_pattern(rest, &tail[index..]))
+        }
+    }
+}
+
+fn is_link_local(address: &str) -> bool {
+    address.starts_with("fe80:")
+}
+
+// Link-local IPv6 addresses are left out unless there is nothing else.
+fn shown_addresses(interface: &ModuleData) -> Vec<(String, String)> {
+    let addresses: Vec<(String, String)> = interface
+        .get("addresses")
+        .and_then(Value::as_list)
+        .unwrap_or_default()
+        .iter()
+        .map(|address| {
+            let cidr = format!("{}/{}", address.text("address"), address.text("prefix"));
+            (address.text("family"), cidr)
+        })
+        .collect();
+
+    let global: Vec<(String, String)> = addresses
+        .iter()
+        .filter(|(_, cidr)| !is_link_local(cidr))
+        .cloned()
+        .collect();
+    if global.is_empty() { addresses } else { global }
+}
+
+fn describe(interface: &ModuleData) -> String {
+    let addresses: Vec<String> = shown_addresses(interface)
+        .into_iter()
+        .map(|(_, cidr)| cidr)
+        .collect();
+
+    let mut details = Vec::new();
+    if let Some(mac) = interface.get("mac") {
+        details.push(mac.to_string());
+    }
+    if let Some(speed) = interface.get("speed_mbps") {
+        details.push(format!("{} Mb/s", speed));
+    }
+    if let Some(state) = interface.get("state") {
+        details.push(state.to_string());
+    }
+
+    let mut text = format!("{}: {}", interface.text("name"), addresses.join(", "));
+    if !details.is_empty() {
+        text.push_str(&format!(" ({})", details.join(", ")));
+    }
+    text
+}
+
+pub struct NetworkModule {
+    hide: Vec<String>,
+}
+
+impl NetworkModule {
+    pub fn new(cfg: &Config) -> Self {
+        Self {
+            hide: cfg.network.hide.clone(),
+        }
+    }
+}
+
+impl InfoModule for NetworkModule {
+    fn id(&self) -> &'static str {
+        "network"
+    }
+
+    fn label(&self) -> &'static str {
+        "Network"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f0200}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let interfaces: Vec<ModuleData> = get_interfaces(&self.hide)
+            .into_iter()
+            .map(|interface| {
+                let addresses = interface
+                    .addresses
+                    .iter()
+                    .map(|address| {
+                        let family = if address.address.is_ipv4() { "ipv4" } else { "ipv6" };
+                        ModuleData::default()
+                            .with("family", family)
+                            .with("address", address.address.to_string())
+                            .with("prefix", Value::Integer(address.prefix as u64))
+                    })
+                    .collect();
+
+                let mut data = ModuleData::default()
+                    .with("name", interface.name)
+                    .with("addresses", Value::List(addresses));
+                if let Some(mac) = interface.mac {
+                    data = data.with("mac", mac);
+                }
+                if let Some(speed) = interface.speed_mbps {
+                    data = data.with("speed_mbps", Value::Integer(speed));
+                }
+                if let Some(state) = interface.state {
+                    data = data.with("state", state);
+                }
+                data
+            })
+            .collect();
+
+        if interfaces.is_empty() {
+            return Err(ModuleError::Unavailable("no active interfaces".into()));
+        }
+
+        Ok(ModuleData::default().with("interfaces", Value::List(interfaces)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        self.render_lines(data, cfg).join(", ")
+    }
+
+    fn render_lines(&self, data: &ModuleData, cfg: &Config) -> Vec<String> {
+        data.get("interfaces")
+            .and_then(Value::as_list)
+            .unwrap_or_default()
+            .iter()
+            .map(|interface| paint_info(&describe(interface), cfg))
+            .collect()
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["name", "addresses", "ipv4", "ipv6", "mac", "speed_mbps", "state"]
+    }
+
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        list_items(data, "interfaces")
+            .into_iter()
+            .map(|mut interface| {
+                let addresses = shown_addresses(&interface);
+                let first = |family: &str| {
+                    addresses
+                        .iter()
+                        .find(|(f, _)| f == family)
+                        .map(|(_, cidr)| cidr.clone())
+                        .unwrap_or_default()
+                };
+                let (ipv4, ipv6) = (first("ipv4"), first("ipv6"));
+                let all: Vec<String> = addresses.iter().map(|(_, cidr)| cidr.clone()).collect();
+
+                interface.set("addresses", all.join(", "));
+                interface.with("ipv4", ipv4).with("ipv6", ipv6)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_hide_patterns() {
+        assert!(matches_pattern("lo", "lo"));
+        assert!(!matches_pattern("lo", "lo0"));
+        assert!(matches_pattern("br-*", "br-3f2a91"));
+        assert!(!matches_pattern("br-*", "bridge0"));
+        assert!(matches_pattern("*0", "eth0"));
+        assert!(!matches_pattern("*0", "eth1"));
+        assert!(matches_pattern("veth*", "veth"));
+        assert!(matches_pattern("veth*", "veth12ab"));
+        assert!(!matches_pattern("veth*", "eth0"));
+    }
+
+    #[test]
+    fn describes_addresses_and_link_details() {
+        let address = |family: &str, address: &str, prefix| {
+            ModuleData::default()
+                .with("family", family)
+                .with("address", address)
+                .with("prefix", Value::Integer(prefix))
+        };
+        let interface = ModuleData::default()
+            .with("name", "eth0")
+            .with(
+                "addresses",
+                Value::List(vec![
+                    address("ipv4", "192.168.1.20", 24),
+                    address("ipv6", "fe80::1", 64),
+                ]),
+            )
+            .with("mac", "52:54:00:12:34:56")
+            .with("speed_mbps", Value::Integer(1000))
+            .with("state", "up");
+        assert_eq!(
+            describe(&interface),
+            "eth0: 192.168.1.20/24 (52:54:00:12:34:56, 1000 Mb/s, up)"
+        );
+
+        let tunnel = ModuleData::default()
+            .with("name", "wg0")
+            .with("addresses", Value::List(vec![address("ipv6", "fe80::2", 64)]));
+        assert_eq!(describe(&tunnel), "wg0: fe80::2/64");
+    }
+}
@@ -3,6 +3,10 @@ use std::io;
 use std::path::Path;
 use std::process::Command;
 
+use crate::module::{InfoModule, ModuleData, ModuleError};
+
+const OS_RELEASE_PATHS: [&str; 2] = ["/etc/os-release", "/usr/lib/os-release"];
+
 pub fn get_os_info() -> io::Result<String> {
     if let Some(android_info) = detect_android() {
         return Ok(android_info);
@@ -59,27 +63,53 @@ fn get_property(key: &str) -> Option<String> {
     ];
 
     for path in &paths {
-        if let Ok(content) = fs::read_to_string(path) {
-            if let Some(value) = parse_prop(&content, key) {
-                return Some(value);
-            }
+        if let Ok(content) = fs::read_to_string(path)
+            && let Some(value) = parse_prop(&content, key)
+        {
+            return Some(value);
         }
     }
     None
 }
 
 
-fn try_read_standard_release() -> io::Result<String> {
-    let paths = [
-        "/etc/os-release",
-        "/usr/lib/os-release",
-    ];
+// ID first, then the ID_LIKE parents in order, e.g. ["pop", "ubuntu", "debian"].
+pub fn get_os_ids() -> Vec<String> {
+    if is_android() {
+        return vec!["android".to_string()];
+    }
 
-    for path in &paths {
-        if let Ok(content) = fs::read_to_string(path) {
-            if let Some(info) = parse_standard_release(&content) {
-                return Ok(info);
-            }
+    let Some(content) = read_os_release() else {
+        return Vec::new();
+    };
+
+    let mut ids = Vec::new();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim_matches(|c| c == '"' || c == '\'');
+        match key {
+            "ID" => ids.insert(0, value.to_lowercase()),
+            "ID_LIKE" => ids.extend(value.split_whitespace().map(|id| id.to_lowercase())),
+            _ => {}
+        }
+    }
+    ids
+}
+
+fn read_os_release() -> Option<String> {
+    OS_RELEASE_PATHS
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+}
+
+fn try_read_standard_release() -> io::Result<String> {
+    for path in &OS_RELEASE_PATHS {
+        if let Ok(content) = fs::read_to_string(path)
+            && let Some(info) = parse_standard_release(&content)
+        {
+            return Ok(info);
         }
     }
     Err(io::Error::new(io::ErrorKind::NotFound, "No standard release file found"))
@@ -126,10 +156,10 @@ fn try_read_legacy_release() -> io::Result<String> {
     ];
 
     for path in &paths {
-        if let Ok(content) = fs::read_to_string(path) {
-            if let Some(info) = parse_legacy_release(&content) {
-                return Ok(info);
-            }
+        if let Ok(content) = fs::read_to_string(path)
+            && let Some(info) = parse_legacy_release(&content)
+        {
+            return Ok(info);
         }
     }
     Err(io::Error::new(io::ErrorKind::NotFound, "No legacy release file found"))
@@ -138,7 +168,7 @@ fn try_read_legacy_release() -> io::Result<String> {
 fn parse_legacy_release(content: &str) -> Option<String> {
     content.lines()
         .find(|line| line.starts_with("DISTRIB_DESCRIPTION"))
-        .and_then(|line| line.splitn(2, '=').nth(1))
+        .and_then(|line| line.split_once('=').map(|(_, value)| value))
         .map(|s| s.trim_matches('"').to_string())
         .or_else(|| content.lines().next().map(|s| s.to_string()))
 }
@@ -151,7 +181,31 @@ fn try_read_debian_version() -> io::Result<String> {
 fn parse_prop(content: &str, key: &str) -> Option<String> {
     content.lines()
         .find(|line| line.starts_with(key))
-        .and_then(|line| line.splitn(2, '=').nth(1))
+        .and_then(|line| line.split_once('=').map(|(_, value)| value))
         .map(|value| value.trim().trim_matches('"').to_string())
         .filter(|s| !s.is_empty())
 }
+
+pub struct OsModule;
+
+impl InfoModule for OsModule {
+    fn id(&self) -> &'static str {
+        "os"
+    }
+
+    fn label(&self) -> &'static str {
+        "OS"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f31a}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        Ok(ModuleData::default().with("name", get_os_info()?))
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["name"]
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::Config;
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError, Value};
+
+const TERMUX_PREFIX: &str = "/data/data/com.termux/files/usr";
+
+#[derive(Debug, Clone)]
+pub struct PackageCount {
+    pub manager: &'static str,
+    pub count: u64,
+}
+
+pub fn get_package_counts() -> Vec<PackageCount> {
+    let home = dirs::home_dir();
+    let in_home = |path: &str| home.as_ref().map(|home| home.join(path));
+
+    let counts = [
+        ("dpkg", count_dpkg(Path::new("/var/lib/dpkg/status"))),
+        ("pkg", count_dpkg(&Path::new(TERMUX_PREFIX).join("var/lib/dpkg/status"))),
+        ("rpm", count_rpm()),
+        ("pacman", count_dirs(Path::new("/var/lib/pacman/local"))),
+        ("apk", count_apk(Path::new("/lib/apk/db/installed"))),
+        ("xbps", count_xbps(Path::new("/var/db/xbps"))),
+        ("flatpak", count_flatpak(Path::new("/var/lib/flatpak"))),
+        (
+            "flatpak-user",
+            in_home(".local/share/flatpak").and_then(|path| count_flatpak(&path)),
+        ),
+        ("snap", count_snap(Path::new("/var/lib/snapd/snaps"))),
+        ("nix-system", count_nix(Path::new("/run/current-system/sw"))),
+        ("nix-default", count_nix(Path::new("/nix/var/nix/profiles/default"))),
+        ("nix-user", in_home(".nix-profile").and_then(|path| count_nix(&path))),
+    ];
+
+    counts
+        .into_iter()
+        .filter_map(|(manager, count)| {
+            let count = count.filter(|&count| count > 0)?;
+            Some(PackageCount { manager, count })
+        })
+        .collect()
+}
+
+// Each package is one stanza; removed packages keep theirs with a
+// "deinstall" or "config-files" status.
+fn count_dpkg(status: &Path) -> Option<u64> {
+    let content = fs::read_to_string(status).ok()?;
+    Some(
+        content
+            .lines()
+            .filter(|line| line.starts_with("Status: ") && line.ends_with(" installed"))
+            .count() as u64,
+    )
+}
+
+// The rpm database is a SQLite (or older Berkeley DB) file, so this is the
+// one manager that goes through its own tool.
+fn count_rpm() -> Option<u64> {
+    if !Path::new("/var/lib/rpm").is_dir() && !Path::new("/usr/lib/sysimage/rpm").is_dir() {
+        return None;
+    }
+    let output = Command::new("rpm").args(["-qa", "--nodigest", "--nosignature"]).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).lines().count() as u64)
+}
+
+fn count_dirs(path: &Path) -> Option<u64> {
+    let entries = fs::read_dir(path).ok()?;
+    Some(
+        entries
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .count() as u64,
+    )
+}
+
+fn count_apk(installed: &Path) -> Option<u64> {
+    let content = fs::read_to_string(installed).ok()?;
+    Some(content.lines().filter(|line| line.starts_with("P:")).count() as u64)
+}
+
+// pkgdb-<version>.plist holds one dictionary per installed package, each
+// with a pkgver key.
+fn count_xbps(db: &Path) -> Option<u64> {
+    let entries = fs::read_dir(db).ok()?;
+    let pkgdb = entries.flatten().map(|entry| entry.path()).find(|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("pkgdb-") && name.ends_with(".plist"))
+    })?;
+    let content = fs::read_to_string(pkgdb).ok()?;
+    Some(content.matches("<key>pkgver</key>").count() as u64)
+}
+
+// Apps and runtimes are both installed refs; every installed branch of a
+// runtime counts separately, as `flatpak list` does.
+fn count_flatpak(installation: &Path) -> Option<u64> {
+    let mut total = 0;
+    let mut found = false;
+    for kind in ["app", "runtime"] {
+        let Ok(refs) = fs::read_dir(installation.join(kind)) else {
+            continue;
+        };
+        found = true;
+        for entry in refs.flatten() {
+            let Ok(arches) = fs::read_dir(entry.path()) else {
+                continue;
+            };
+            for arch in arches.flatten() {
+                total += count_dirs(&arch.path()).unwrap_or(0);
+            }
+        }
+    }
+    found.then_some(total)
+}
+
+// Every revision is kept as <name>_<revision>.snap.
+fn count_snap(snaps: &Path) -> Option<u64> {
+    let entries = fs::read_dir(snaps).ok()?;
+    let names: BTreeSet<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stem = name.strip_suffix(".snap")?;
+            Some(stem.rsplit_once('_').map_or(stem, |(name, _)| name).to_string())
+        })
+        .collect();
+    Some(names.len() as u64)
+}
+
+// Profiles created by `nix profile` list their packages in manifest.json;
+// older ones are counted through the store closure.
+fn count_nix(profile: &Path) -> Option<u64> {
+    if !profile.exists() {
+        return None;
+    }
+
+    if let Ok(manifest) = fs::read_to_string(profile.join("manifest.json"))
+        && let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&manifest)
+    {
+        let elements = &manifest["elements"];
+        if let Some(elements) = elements.as_object() {
+            return Some(elements.len() as u64);
+        }
+        if let Some(elements) = elements.as_array() {
+            return Some(elements.len() as u64);
+        }
+    }
+
+    let target = fs::canonicalize(profile).ok()?;
+    let output = Command::new("nix-store")
+        .arg("--query")
+        .arg("--requisites")
+        .arg(target)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).lines().count() as u64)
+}
+
+fn describe(data: &ModuleData) -> String {
+    data.get("managers")
+        .and_then(Value::as_list)
+        .unwrap_or_default()
+        .iter()
+        .map(|manager| format!("{} ({})", manager.text("count"), manager.text("manager")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub struct PackagesModule;
+
+impl InfoModule for PackagesModule {
+    fn id(&self) -> &'static str {
+        "packages"
+    }
+
+    fn label(&self) -> &'static str {
+        "Packages"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f03d7}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let counts = get_package_counts();
+        if counts.is_empty() {
+            return Err(ModuleError::Unavailable("no package manager found".into()));
+        }
+
+        let total = counts.iter().map(|count| count.count).sum();
+        let managers = counts
+            .into_iter()
+            .map(|count| {
+                ModuleData::default()
+                    .with("manager", count.manager)
+                    .with("count", Value::Integer(count.count))
+            })
+            .collect();
+
+        Ok(ModuleData::default()
+            .with("total", Value::Integer(total))
+            .with("managers", Value::List(managers)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        paint_info(&describe(data), cfg)
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["total", "managers"]
+    }
+
+    fn template_values(&self, data: &ModuleData, _cfg: &Config) -> Vec<ModuleData> {
+        let mut values = data.clone();
+        values.set("managers", describe(data));
+        vec![values]
+    }
+}
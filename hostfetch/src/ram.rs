@@ -1,11 +1,15 @@
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 
+use crate::config::Config;
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError, Value};
+
 const BOLD: &str = "\x1b[1m";
 const GREEN: &str = "\x1b[32m";
 const YELLOW: &str = "\x1b[33m";
 const RED: &str = "\x1b[31m";
 const RESET: &str = "\x1b[0m";
 
+#[derive(Debug, Clone)]
 pub struct MemoryData {
     used_bytes: u64,
     total_bytes: u64,
@@ -34,20 +38,87 @@ impl MemoryData {
         }
     }
 
+    pub fn used_bytes(&self) -> u64 {
+        self.used_bytes
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    pub fn percent(&self) -> f64 {
+        self.percent
+    }
+
     pub fn formatted_usage(&self) -> String {
+        format_usage(self.used_bytes, self.total_bytes)
+    }
+
+    pub fn formatted_percent(&self) -> String {
+        format_percent(self.percent)
+    }
+}
+
+impl Default for MemoryData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_usage(used: u64, total: u64) -> String {
+    format!(
+        "{:.1} GB / {:.1} GB",
+        used as f64 / 1e9,
+        total as f64 / 1e9
+    )
+}
+
+fn format_percent(percent: f64) -> String {
+    let color = match percent {
+        p if p < 50.0 => GREEN,
+        p if p < 75.0 => YELLOW,
+        _ => RED,
+    };
+    format!("{}{}{BOLD}{:.0}%{}", color, BOLD, percent, RESET)
+}
+
+pub struct RamModule;
+
+impl InfoModule for RamModule {
+    fn id(&self) -> &'static str {
+        "ram"
+    }
+
+    fn label(&self) -> &'static str {
+        "RAM"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f035b}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let mem = MemoryData::new();
+
+        Ok(ModuleData::default()
+            .with("used", Value::Bytes(mem.used_bytes))
+            .with("total", Value::Bytes(mem.total_bytes))
+            .with("percent", Value::Percent(mem.percent)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        let used = data.get("used").and_then(Value::as_u64).unwrap_or(0);
+        let total = data.get("total").and_then(Value::as_u64).unwrap_or(0);
+        let percent = data.get("percent").and_then(Value::as_f64).unwrap_or(0.0);
+
         format!(
-            "{:.1} GB / {:.1} GB",
-            self.used_bytes as f64 / 1e9,
-            self.total_bytes as f64 / 1e9
+            "{} ({})",
+            paint_info(&format_usage(used, total), cfg),
+            format_percent(percent)
         )
     }
 
-    pub fn formatted_percent(&self) -> String {
-        let color = match self.percent {
-            p if p < 50.0 => GREEN,
-            p if p < 75.0 => YELLOW,
-            _ => RED,
-        };
-        format!("{}{}{BOLD}{:.0}%{}", color, BOLD, self.percent, RESET)
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["used", "total", "percent"]
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,36 @@
 use std::env;
 
+use crate::module::{InfoModule, ModuleData, ModuleError};
+
 pub fn get_shell_name() -> String {
     env::var("SHELL")
         .unwrap_or_else(|_| "unknown".into())
         .split('/')
-        .last()
+        .next_back()
         .unwrap_or("unknown")
         .to_string()
-}
\ No newline at end of file
+}
+
+pub struct ShellModule;
+
+impl InfoModule for ShellModule {
+    fn id(&self) -> &'static str {
+        "shell"
+    }
+
+    fn label(&self) -> &'static str {
+        "Shell"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{e691}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        Ok(ModuleData::default().with("name", get_shell_name()))
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["name"]
+    }
+}
@@ -1,7 +1,42 @@
 use sysinfo::System;
-use owo_colors::{OwoColorize, Style};
 
-pub fn get_swap_info() -> Option<(String, String)> {
+use crate::config::Config;
+use crate::module::{format_bytes, paint_info, paint_percent, InfoModule, ModuleData, ModuleError, Value};
+
+#[derive(Debug, Clone)]
+pub struct SwapData {
+    used_bytes: u64,
+    total_bytes: u64,
+    percent: f64,
+}
+
+impl SwapData {
+    pub fn used_bytes(&self) -> u64 {
+        self.used_bytes
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    pub fn percent(&self) -> f64 {
+        self.percent
+    }
+
+    pub fn formatted_usage(&self) -> String {
+        format_usage(self.used_bytes, self.total_bytes)
+    }
+
+    pub fn formatted_percent(&self) -> String {
+        paint_percent(self.percent)
+    }
+}
+
+fn format_usage(used: u64, total: u64) -> String {
+    format!("{} / {}", format_bytes(used), format_bytes(total))
+}
+
+pub fn get_swap_info() -> Option<SwapData> {
     let mut system = System::new();
     system.refresh_memory();
 
@@ -13,46 +48,59 @@ pub fn get_swap_info() -> Option<(String, String)> {
     }
 
     let used = total - free;
-    let ratio = format!("{} / {}", format_size(used), format_size(total));
-    
     let percent = (used as f64 / total as f64) * 100.0;
-    let percent_value = format!("{:.0}%", percent);
-    
-    let bold_style = Style::new().bold();
-    
-    let colored_percent = if percent <= 50.0 {
-        percent_value
-            .style(bold_style
-                .green())
-            .to_string()
-    } else if percent <= 75.0 {
-        percent_value
-            .style(bold_style
-                .yellow())
-            .to_string()
-    } else {
-        percent_value
-            .style(bold_style
-                .red())
-            .to_string()
-    };
-
-    Some((ratio, colored_percent))
+
+    Some(SwapData {
+        used_bytes: used,
+        total_bytes: total,
+        percent,
+    })
 }
 
-fn format_size(bytes: u64) -> String {
-    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
+pub struct SwapModule;
+
+impl InfoModule for SwapModule {
+    fn id(&self) -> &'static str {
+        "swap"
+    }
+
+    fn label(&self) -> &'static str {
+        "Swap"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{ebcb}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        let (used, total, percent) = match get_swap_info() {
+            Some(swap) => (swap.used_bytes, swap.total_bytes, swap.percent),
+            None => (0, 0, 0.0),
+        };
 
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
+        Ok(ModuleData::default()
+            .with("used", Value::Bytes(used))
+            .with("total", Value::Bytes(total))
+            .with("percent", Value::Percent(percent)))
     }
 
-    if size.fract() == 0.0 || unit_index == 0 {
-        format!("{:.0} {}", size, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        let used = data.get("used").and_then(Value::as_u64).unwrap_or(0);
+        let total = data.get("total").and_then(Value::as_u64).unwrap_or(0);
+        let percent = data.get("percent").and_then(Value::as_f64).unwrap_or(0.0);
+
+        if total == 0 {
+            return format!("{} (0%)", paint_info("No swap", cfg));
+        }
+
+        format!(
+            "{} ({})",
+            paint_info(&format_usage(used, total), cfg),
+            paint_percent(percent)
+        )
     }
-}
\ No newline at end of file
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["used", "total", "percent"]
+    }
+}
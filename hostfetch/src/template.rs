@@ -0,0 +1,117 @@
+use crate::module::ModuleData;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Field(&'a str),
+}
+
+// "{name}" is replaced by a field; "{{" and "}}" stand for literal braces.
+pub fn parse(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        if index > 0 {
+            segments.push(Segment::Text(&rest[..index]));
+        }
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+
+        if let Some(after) = rest.strip_prefix(brace) {
+            segments.push(Segment::Text(brace));
+            rest = after;
+            continue;
+        }
+        if brace == "}" {
+            return Err("unmatched '}' (write '}}' for a literal brace)".to_string());
+        }
+
+        let end = rest.find('}').ok_or("unclosed '{'")?;
+        let name = rest[..end].trim();
+        if name.is_empty() {
+            return Err("empty placeholder '{}'".to_string());
+        }
+        if name.contains('{') {
+            return Err(format!("unclosed '{{' before '{}'", name));
+        }
+        segments.push(Segment::Field(name));
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+pub fn placeholders(template: &str) -> Result<Vec<&str>, String> {
+    Ok(parse(template)?
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Field(name) => Some(name),
+            Segment::Text(_) => None,
+        })
+        .collect())
+}
+
+// Fields the data does not have (optional ones, or an invalid template)
+// render as nothing.
+pub fn render(template: &str, values: &ModuleData) -> String {
+    let Ok(segments) = parse(template) else {
+        return template.to_string();
+    };
+
+    segments
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.to_string(),
+            Segment::Field(name) => values.text(name),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_text_and_fields() {
+        assert_eq!(
+            parse("{used} / { total }").unwrap(),
+            vec![Segment::Field("used"), Segment::Text(" / "), Segment::Field("total")]
+        );
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        assert_eq!(
+            parse("{{used}}").unwrap(),
+            vec![Segment::Text("{"), Segment::Text("used"), Segment::Text("}")]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_templates() {
+        assert_eq!(parse("{used"), Err("unclosed '{'".to_string()));
+        assert_eq!(parse("{used {total}"), Err("unclosed '{' before 'used {total'".to_string()));
+        assert_eq!(
+            parse("used}"),
+            Err("unmatched '}' (write '}}' for a literal brace)".to_string())
+        );
+        assert_eq!(parse("{}"), Err("empty placeholder '{}'".to_string()));
+        assert_eq!(parse("{ }"), Err("empty placeholder '{}'".to_string()));
+    }
+
+    #[test]
+    fn lists_placeholders() {
+        assert_eq!(placeholders("{name}: {percent}").unwrap(), vec!["name", "percent"]);
+    }
+
+    #[test]
+    fn missing_fields_render_empty() {
+        let values = ModuleData::default().with("percent", "80%");
+        assert_eq!(render("{percent} ({time_remaining})", &values), "80% ()");
+        assert_eq!(render("{{{percent}}}", &values), "{80%}");
+    }
+}
@@ -1,5 +1,7 @@
 use std::env;
-use std::process::Command;
+
+use crate::module::{InfoModule, ModuleData, ModuleError};
+use crate::process;
 
 pub fn detect_terminal() -> String {
     let detectors = [
@@ -9,12 +11,14 @@ pub fn detect_terminal() -> String {
         ("KITTY_PID", "Kitty"),
         ("TERMUX_VERSION", "Termux"),
         ("WEZTERM_EXECUTABLE", "WezTerm"),
+        ("XTERM_VERSION", "XTerm"),
+        ("MLTERM", "mlterm"),
         ("TERM_PROGRAM", ""),
         ("TERM", ""),  
     ];
 
     for (var, name) in &detectors {
-        if let Ok(_) = env::var(var) {
+        if env::var(var).is_ok() {
             return if name.is_empty() {
                 env::var(var).unwrap_or_else(|_| "unknown".into())
             } else {
@@ -23,34 +27,26 @@ pub fn detect_terminal() -> String {
         }
     }
 
-    if let Ok(ppid) = get_parent_pid() {
-        let output = Command::new("ps")
-            .args(["-o", "comm=", "-p", &ppid.to_string()])
-            .output()
-            .ok()
-            .and_then(|o| String::from_utf8(o.stdout).ok());
-
-        if let Some(cmd) = output {
-            let proc_name = cmd.trim().to_lowercase();
-            let known_terminals = [
-                ("gnome-terminal", "GNOME Terminal"),
-                ("xterm", "XTerm"),
-                ("tilix", "Tilix"),
-                ("terminator", "Terminator"),
-                ("xfce4-terminal", "XFCE Terminal"),
-                ("urxvt", "URxvt"),
-                ("st", "ST"),
-                ("kitty", "Kitty"),
-                ("alacritty", "Alacritty"),
-                ("termux", "Termux"),
-                ("wezterm", "WezTerm"),
-                ("lxterminal", "LXTerminal"),
-            ];
+    if let Some(cmd) = process::parent_pid().and_then(process::process_name) {
+        let proc_name = cmd.to_lowercase();
+        let known_terminals = [
+            ("gnome-terminal", "GNOME Terminal"),
+            ("xterm", "XTerm"),
+            ("tilix", "Tilix"),
+            ("terminator", "Terminator"),
+            ("xfce4-terminal", "XFCE Terminal"),
+            ("urxvt", "URxvt"),
+            ("st", "ST"),
+            ("kitty", "Kitty"),
+            ("alacritty", "Alacritty"),
+            ("termux", "Termux"),
+            ("wezterm", "WezTerm"),
+            ("lxterminal", "LXTerminal"),
+        ];
 
-            for (pattern, name) in &known_terminals {
-                if proc_name.contains(pattern) {
-                    return name.to_string();
-                }
+        for (pattern, name) in &known_terminals {
+            if proc_name.contains(pattern) {
+                return name.to_string();
             }
         }
     }
@@ -58,10 +54,26 @@ pub fn detect_terminal() -> String {
     env::var("TERM").unwrap_or_else(|_| "unknown".into())
 }
 
-fn get_parent_pid() -> Result<u32, ()> {
-    let stat = std::fs::read_to_string("/proc/self/stat").map_err(|_| ())?;
-    stat.split(' ')
-        .nth(3)
-        .and_then(|s| s.parse().ok())
-        .ok_or(())
-}
\ No newline at end of file
+pub struct TerminalModule;
+
+impl InfoModule for TerminalModule {
+    fn id(&self) -> &'static str {
+        "terminal"
+    }
+
+    fn label(&self) -> &'static str {
+        "Terminal"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f489}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        Ok(ModuleData::default().with("name", detect_terminal()))
+    }
+
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["name"]
+    }
+}
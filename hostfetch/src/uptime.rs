@@ -1,19 +1,151 @@
-use std::process::Command;
-use std::io::{self, Error, ErrorKind};
+use std::fs;
+use std::io;
+use std::mem::MaybeUninit;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn get_uptime() -> io::Result<String> {
-    let output = Command::new("uptime")
-        .arg("-p")
-        .output()?;
+use nix::sys::sysinfo::sysinfo;
 
-    if !output.status.success() {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        return Err(Error::new(ErrorKind::Other, error_msg.into_owned()));
+use crate::config::{Config, UptimeStyle};
+use crate::module::{paint_info, InfoModule, ModuleData, ModuleError, Value};
+
+pub fn get_uptime() -> io::Result<u64> {
+    if let Ok(content) = fs::read_to_string("/proc/uptime")
+        && let Some(seconds) = content
+            .split_whitespace()
+            .next()
+            .and_then(|s| s.parse::<f64>().ok())
+    {
+        return Ok(seconds as u64);
+    }
+
+    sysinfo()
+        .map(|info| info.uptime().as_secs())
+        .map_err(io::Error::from)
+}
+
+pub fn format_uptime(seconds: u64, style: UptimeStyle) -> String {
+    let days = seconds / 86400;
+    let hours = seconds % 86400 / 3600;
+    let minutes = seconds % 3600 / 60;
+
+    match style {
+        UptimeStyle::Long => {
+            let parts: Vec<String> = [(days, "day"), (hours, "hour"), (minutes, "minute")]
+                .iter()
+                .filter(|(value, _)| *value > 0)
+                .map(|(value, unit)| format!("{} {}{}", value, unit, if *value == 1 { "" } else { "s" }))
+                .collect();
+
+            if parts.is_empty() {
+                "0 minutes".to_string()
+            } else {
+                parts.join(", ")
+            }
+        }
+        UptimeStyle::Short => {
+            let parts: Vec<String> = [(days, "d"), (hours, "h"), (minutes, "m")]
+                .iter()
+                .filter(|(value, _)| *value > 0)
+                .map(|(value, unit)| format!("{}{}", value, unit))
+                .collect();
+
+            if parts.is_empty() {
+                "0m".to_string()
+            } else {
+                parts.join(" ")
+            }
+        }
+        UptimeStyle::Clock => format!("{}:{:02}", seconds / 3600, minutes),
+        UptimeStyle::Since => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            format_boot_time(now.saturating_sub(seconds))
+        }
+    }
+}
+
+fn format_boot_time(boot: u64) -> String {
+    let time = boot as libc::time_t;
+    let mut tm = MaybeUninit::<libc::tm>::uninit();
+
+    let tm = unsafe {
+        if libc::localtime_r(&time, tm.as_mut_ptr()).is_null() {
+            return "unknown".to_string();
+        }
+        tm.assume_init()
+    };
+
+    format!(
+        "since {:04}-{:02}-{:02} {:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min
+    )
+}
+
+pub struct UptimeModule;
+
+impl InfoModule for UptimeModule {
+    fn id(&self) -> &'static str {
+        "uptime"
     }
 
-    let uptime = String::from_utf8_lossy(&output.stdout)
-        .trim()
-        .to_string();
+    fn label(&self) -> &'static str {
+        "Uptime"
+    }
+
+    fn icon(&self) -> &'static str {
+        "\u{f43a}"
+    }
+
+    fn collect(&self) -> Result<ModuleData, ModuleError> {
+        Ok(ModuleData::default().with("seconds", Value::Integer(get_uptime()?)))
+    }
+
+    fn render(&self, data: &ModuleData, cfg: &Config) -> String {
+        let seconds = data.get("seconds").and_then(Value::as_u64).unwrap_or(0);
+        paint_info(&format_uptime(seconds, cfg.uptime_style()), cfg)
+    }
 
-    Ok(uptime)
-}
\ No newline at end of file
+    fn placeholders(&self) -> &'static [&'static str] {
+        &["uptime", "seconds", "days", "hours", "minutes"]
+    }
+
+    fn template_values(&self, data: &ModuleData, cfg: &Config) -> Vec<ModuleData> {
+        let seconds = data.get("seconds").and_then(Value::as_u64).unwrap_or(0);
+        let values = data
+            .clone()
+            .with("uptime", format_uptime(seconds, cfg.uptime_style()))
+            .with("days", Value::Integer(seconds / 86400))
+            .with("hours", Value::Integer(seconds % 86400 / 3600))
+            .with("minutes", Value::Integer(seconds % 3600 / 60));
+        vec![values]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_each_style() {
+        let cases = [
+            (0, "0 minutes", "0m", "0:00"),
+            (60, "1 minute", "1m", "0:01"),
+            (3600, "1 hour", "1h", "1:00"),
+            (86400, "1 day", "1d", "24:00"),
+            (2 * 86400 + 7200 + 180, "2 days, 2 hours, 3 minutes", "2d 2h 3m", "50:03"),
+            (76 * 3600 + 12 * 60, "3 days, 4 hours, 12 minutes", "3d 4h 12m", "76:12"),
+        ];
+        for (seconds, long, short, clock) in cases {
+            assert_eq!(format_uptime(seconds, UptimeStyle::Long), long);
+            assert_eq!(format_uptime(seconds, UptimeStyle::Short), short);
+            assert_eq!(format_uptime(seconds, UptimeStyle::Clock), clock);
+        }
+        assert!(format_uptime(3600, UptimeStyle::Since).starts_with("since "));
+    }
+}
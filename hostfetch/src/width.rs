@@ -0,0 +1,49 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+
+// Terminal columns taken by text without escape sequences. Each grapheme
+// cluster is measured on its own so combining marks add nothing and emoji
+// sequences count once. Nerd Font glyphs live in the private use areas,
+// which have no defined width; terminals draw them as one or two cells.
+pub fn display_width(text: &str, icon_width: usize) -> usize {
+    text.graphemes(true)
+        .map(|grapheme| {
+            let first = grapheme.chars().next().unwrap_or(' ');
+            if is_private_use(first) {
+                icon_width
+            } else if grapheme.contains(ZERO_WIDTH_JOINER) || grapheme.contains(EMOJI_PRESENTATION) {
+                2
+            } else {
+                grapheme.width()
+            }
+        })
+        .sum()
+}
+
+fn is_private_use(c: char) -> bool {
+    matches!(c, '\u{e000}'..='\u{f8ff}' | '\u{f0000}'..='\u{ffffd}' | '\u{100000}'..='\u{10fffd}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_graphemes() {
+        assert_eq!(display_width("hostfetch", 1), 9);
+        assert_eq!(display_width("漢字", 1), 4);
+        assert_eq!(display_width("e\u{301}", 1), 1);
+        assert_eq!(display_width("👩\u{200d}💻", 1), 2);
+        assert_eq!(display_width("❤\u{fe0f}", 1), 2);
+    }
+
+    #[test]
+    fn nerd_font_icons_take_icon_width() {
+        assert_eq!(display_width("\u{f489} Terminal", 1), 10);
+        assert_eq!(display_width("\u{f489} Terminal", 2), 11);
+        assert_eq!(display_width("\u{f08ae}", 2), 2);
+    }
+}